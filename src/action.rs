@@ -6,7 +6,11 @@ pub fn duplicate(
     mut commands: Commands,
     mut map: NonSendMut<Map>,
     registry: Res<Registry>,
+    appdata: Res<AppData>,
+    vfs: Res<vfs::Vfs>,
+    mut loading: ResMut<Loading>,
     consts: Res<Constants>,
+    origin: Res<Origin>,
     selected: Query<(Entity, &actor::Actor, &mut Transform), With<actor::Selected>>,
     mut cubes: Query<&mut Handle<wire::Wire>>,
 ) {
@@ -21,15 +25,7 @@ pub fn duplicate(
         return;
     }
     for (entity, actor, ..) in selected.iter() {
-        match cubes.get_mut(entity) {
-            Ok(mut mat) => {
-                commands.entity(entity).remove::<actor::Selected>();
-                *mat = consts.unselected.clone_weak();
-            }
-            Err(_) => {
-                commands.entity(entity).remove::<actor::SelectedBundle>();
-            }
-        }
+        picking::deselect(entity, &mut commands, &mut cubes, &consts);
         let len = map.asset_data.exports.len();
         let insert = unreal_asset::types::PackageIndex::new(len as i32 + 1);
         actor.duplicate(map, export_names);
@@ -39,18 +35,17 @@ pub fn duplicate(
             message: format!("{} duplicated", actor.name),
             kind: Warning,
         });
-        match path {
-            Some(ref path) => {
-                let (mesh, material) = &registry.meshes[path];
+        match path.as_ref().and_then(|path| registry.meshes.get(path)) {
+            Some((mesh, material)) => {
                 commands.spawn((
                     actor::SelectedBundle::default(),
                     MaterialMeshBundle {
-                        mesh: mesh.clone_weak(),
+                        mesh: mesh.clone(),
                         material: material
                             .as_ref()
-                            .map(|mat| registry.mats[mat].clone_weak())
+                            .and_then(|mat| registry.mats.get(mat).cloned())
                             .unwrap_or(consts.grid.clone_weak()),
-                        transform: actor.transform(map),
+                        transform: actor.transform(map, origin.0),
                         ..default()
                     },
                     bevy_mod_raycast::deferred::RaycastMesh::<()>::default(),
@@ -58,52 +53,96 @@ pub fn duplicate(
                 ));
             }
             None => {
-                commands
-                    .spawn((
-                        actor::Selected,
-                        MaterialMeshBundle {
-                            mesh: consts.cube.clone_weak(),
-                            material: consts.selected.clone_weak(),
-                            transform: actor.transform(map),
+                let mut placeholder = commands.spawn((
+                    actor::Selected,
+                    MaterialMeshBundle {
+                        mesh: consts.cube.clone_weak(),
+                        material: consts.selected.clone_weak(),
+                        transform: actor.transform(map, origin.0),
+                        ..default()
+                    },
+                    bevy::pbr::wireframe::NoWireframe,
+                    new,
+                ));
+                // load it if it was released or the paks were reloaded and pick it up once it's ready
+                if let Some(path) = path {
+                    placeholder.insert(actor::Pending(path.clone()));
+                    dialog::queue(
+                        &mut loading,
+                        &vfs,
+                        appdata
+                            .profile()
+                            .cache
+                            .then(|| cache::Cache::new(appdata.profile()))
+                            .flatten(),
+                        path,
+                        appdata.version(),
+                        appdata.textures,
+                    );
+                }
+                placeholder.with_children(|parent| {
+                    parent.spawn((
+                        consts.bounds.clone_weak(),
+                        SpatialBundle {
+                            visibility: Visibility::Hidden,
                             ..default()
                         },
-                        bevy::pbr::wireframe::NoWireframe,
-                        new,
-                    ))
-                    .with_children(|parent| {
-                        parent.spawn((
-                            consts.bounds.clone_weak(),
-                            SpatialBundle {
-                                visibility: Visibility::Hidden,
-                                ..default()
-                            },
-                            bevy_mod_raycast::deferred::RaycastMesh::<()>::default(),
-                        ));
-                    });
+                        bevy_mod_raycast::deferred::RaycastMesh::<()>::default(),
+                    ));
+                });
             }
         }
     }
 }
 
 pub fn delete(
-    _: Trigger<triggers::Delete>,
+    trigger: Trigger<triggers::Delete>,
     mut notif: EventWriter<Notif>,
     mut commands: Commands,
     mut map: NonSendMut<Map>,
-    selected: Query<(Entity, &actor::Actor, &mut Transform), With<actor::Selected>>,
+    mut deleting: ResMut<Deleting>,
+    selected: Query<Entity, With<actor::Selected>>,
+    actors: Query<&actor::Actor>,
 ) {
     let Some((map, ..)) = &mut map.0 else { return };
-    if selected.is_empty() {
+    // a confirmed delete removes what was asked about rather than whatever's selected now
+    let entities: Vec<_> = match (trigger.event().0, deleting.0.take()) {
+        (true, Some((entities, _))) => entities,
+        _ => selected.iter().collect(),
+    };
+    let targets: Vec<_> = entities
+        .into_iter()
+        .filter_map(|entity| actors.get(entity).ok().map(|actor| (entity, actor)))
+        .collect();
+    if targets.is_empty() {
         notif.send(Notif {
             message: "no actors to delete".into(),
             kind: Warning,
         });
         return;
     }
-    for (entity, actor, ..) in selected.iter() {
-        actor.delete(map);
+    // dangling references crash the game so ask before nulling them
+    let owned: Vec<_> = targets
+        .iter()
+        .flat_map(|(_, actor)| actor.owned(map))
+        .collect();
+    let refs = targets
+        .iter()
+        .flat_map(|(_, actor)| actor.referenced_by(map))
+        // references between actors being deleted go with them
+        .filter(|(i, _)| !owned.contains(&unreal_asset::types::PackageIndex::new(*i as i32 + 1)))
+        .count();
+    if !trigger.event().0 && refs != 0 {
+        deleting.0 = Some((targets.iter().map(|(entity, _)| *entity).collect(), refs));
+        return;
+    }
+    for (entity, actor) in targets {
+        let nulled = actor.delete(map);
         notif.send(Notif {
-            message: format!("{} deleted", actor.name),
+            message: match nulled {
+                0 => format!("{} deleted", actor.name),
+                nulled => format!("{} deleted and {nulled} references nulled", actor.name),
+            },
             kind: Warning,
         });
         commands.entity(entity).despawn_recursive()
@@ -148,12 +187,51 @@ pub fn approach(
     }
 }
 
+/// shifts the floating origin to the camera once it strays far enough for f32 to lose precision
+pub fn rebase(
+    mut origin: ResMut<Origin>,
+    mut focus: ResMut<Focus>,
+    drag: Res<Drag>,
+    map: NonSend<Map>,
+    mut camera: Query<
+        (
+            &mut smooth_bevy_cameras::LookTransform,
+            &mut smooth_bevy_cameras::Smoother,
+        ),
+        With<Camera3d>,
+    >,
+    mut actors: Query<(&actor::Actor, &mut Transform)>,
+) {
+    const THRESHOLD: f32 = 1000.0;
+    let (mut camera, mut smoother) = camera.single_mut();
+    // moving the origin mid-drag would invalidate the drag start
+    if camera.eye.length() < THRESHOLD || !matches!(drag.as_ref(), Drag::None) {
+        return;
+    }
+    let shift = camera.eye.round();
+    origin.0 += shift.as_dvec3();
+    camera.eye -= shift;
+    camera.target -= shift;
+    // otherwise the camera would visibly glide across the jump
+    smoother.reset();
+    if let Some(target) = focus.0.as_mut() {
+        *target -= shift;
+    }
+    let Some((map, ..)) = &map.0 else { return };
+    // recalculate from the map rather than subtracting to avoid accumulating error
+    for (actor, mut transform) in actors.iter_mut() {
+        transform.translation = (actor.location(map) - origin.0).as_vec3();
+    }
+}
+
 pub fn copy(
     _: Trigger<triggers::Copy>,
     mut notif: EventWriter<Notif>,
     mut buffer: ResMut<Buffer>,
+    map: NonSend<Map>,
     selected: Query<(Entity, &actor::Actor, &mut Transform), With<actor::Selected>>,
 ) {
+    let Some((map, ..)) = &map.0 else { return };
     if selected.is_empty() {
         notif.send(Notif {
             message: "no actors to copy location from".into(),
@@ -163,8 +241,10 @@ pub fn copy(
     }
     buffer.0 = selected
         .iter()
-        .fold(Vec3::ZERO, |pos, (_, _, trans)| pos + trans.translation)
-        / selected.iter().len() as f32;
+        .fold(bevy::math::DVec3::ZERO, |pos, (_, actor, _)| {
+            pos + actor.location(map)
+        })
+        / selected.iter().len() as f64;
     notif.send(Notif {
         message: "location copied".into(),
         kind: Success,
@@ -176,6 +256,7 @@ pub fn paste(
     mut notif: EventWriter<Notif>,
     mut map: NonSendMut<Map>,
     buffer: Res<Buffer>,
+    origin: Res<Origin>,
     mut selected: Query<(Entity, &actor::Actor, &mut Transform), With<actor::Selected>>,
 ) {
     let Some((map, ..)) = &mut map.0 else { return };
@@ -189,11 +270,13 @@ pub fn paste(
     let offset = buffer.0
         - selected
             .iter()
-            .fold(Vec3::ZERO, |pos, (_, _, trans)| pos + trans.translation)
-            / selected.iter().len() as f32;
+            .fold(bevy::math::DVec3::ZERO, |pos, (_, actor, _)| {
+                pos + actor.location(map)
+            })
+            / selected.iter().len() as f64;
     for (_, actor, mut trans) in selected.iter_mut() {
         actor.add_location(map, offset);
-        trans.translation += offset;
+        trans.translation = (actor.location(map) - origin.0).as_vec3();
     }
     notif.send(Notif {
         message: "location pasted".into(),
@@ -209,15 +292,50 @@ pub fn deselect(
     mut commands: Commands,
 ) {
     for entity in selected.iter() {
-        match cubes.get_mut(entity) {
-            Ok(mut mat) => {
-                commands.entity(entity).remove::<actor::Selected>();
-                *mat = consts.unselected.clone_weak();
-            }
-            Err(_) => {
-                commands.entity(entity).remove::<actor::SelectedBundle>();
-            }
-        }
+        picking::deselect(entity, &mut commands, &mut cubes, &consts);
+    }
+}
+
+pub fn reveal(
+    trigger: Trigger<triggers::Reveal>,
+    mut commands: Commands,
+    mut notif: EventWriter<Notif>,
+    consts: Res<Constants>,
+    map: NonSend<Map>,
+    actors: Query<(Entity, &actor::Actor)>,
+    selected: Query<Entity, With<actor::Selected>>,
+    mut cubes: Query<&mut Handle<wire::Wire>>,
+) {
+    let Some((map, ..)) = &map.0 else { return };
+    let export = trigger.event().export;
+    // components reveal their actor with the component inspected
+    let Some((entity, component)) =
+        actors
+            .iter()
+            .find_map(|(entity, actor)| match actor.export == export {
+                true => Some((entity, None)),
+                false => actor
+                    .components(map)
+                    .iter()
+                    .any(|i| i.index as usize == export + 1)
+                    .then_some((entity, Some(export))),
+            })
+    else {
+        notif.send(Notif {
+            message: "export isn't part of an actor".into(),
+            kind: Warning,
+        });
+        return;
+    };
+    for entity in selected.iter() {
+        picking::deselect(entity, &mut commands, &mut cubes, &consts);
+    }
+    picking::select(entity, &mut commands, &mut cubes, &consts);
+    if let Some(component) = component {
+        commands.entity(entity).insert(actor::Inspected(component));
+    }
+    if trigger.event().focus {
+        commands.trigger(triggers::Focus);
     }
 }
 
@@ -248,121 +366,258 @@ pub fn load_paks(
     _: Trigger<triggers::LoadPaks>,
     mut notif: EventWriter<Notif>,
     appdata: Res<AppData>,
-    mut paks: ResMut<Paks>,
+    mut vfs: ResMut<vfs::Vfs>,
+    mut registry: ResMut<Registry>,
+    mut loading: ResMut<Loading>,
+    mut placing: ResMut<Placing>,
+    mut commands: Commands,
+    pending: Query<Entity, With<actor::Pending>>,
+) {
+    *vfs = vfs::Vfs::mount(appdata.profile(), |message| {
+        notif.send(Notif {
+            message,
+            kind: Warning,
+        });
+    });
+    // actors hold their own handles so only later opens are affected
+    *registry = Registry::default();
+    // paths may mean different assets now so drop whatever the old paks were loading
+    *loading = Loading::default();
+    *placing = Placing::default();
+    for entity in pending.iter() {
+        commands.entity(entity).remove::<actor::Pending>();
+    }
+}
+
+pub fn spawn(
+    trigger: Trigger<triggers::Spawn>,
+    mut notif: EventWriter<Notif>,
+    map: NonSend<Map>,
+    appdata: Res<AppData>,
+    vfs: Res<vfs::Vfs>,
+    registry: Res<Registry>,
+    mut loading: ResMut<Loading>,
+    mut placing: ResMut<Placing>,
+) {
+    let Some((_, path, ..)) = &map.0 else {
+        notif.send(Notif {
+            message: "open a map to place meshes in".into(),
+            kind: Warning,
+        });
+        return;
+    };
+    let triggers::Spawn { mesh, at } = trigger.event();
+    // loading the mesh is what checks it's a static mesh so place it once that's done
+    if !registry.meshes.contains_key(mesh) {
+        dialog::queue(
+            &mut loading,
+            &vfs,
+            appdata
+                .profile()
+                .cache
+                .then(|| cache::Cache::new(appdata.profile()))
+                .flatten(),
+            mesh.clone(),
+            appdata.version(),
+            appdata.textures,
+        );
+    }
+    placing.0.push((path.clone(), mesh.clone(), *at));
+}
+
+/// adds the actors for meshes which have finished loading
+pub fn place(
+    mut commands: Commands,
+    mut notif: EventWriter<Notif>,
+    mut map: NonSendMut<Map>,
+    registry: Res<Registry>,
+    loading: Res<Loading>,
+    mut placing: ResMut<Placing>,
+    consts: Res<Constants>,
+    origin: Res<Origin>,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+    selected: Query<Entity, With<actor::Selected>>,
+    mut cubes: Query<&mut Handle<wire::Wire>>,
 ) {
-    let Some(pak) = appdata.pak else { return };
-    use aes::cipher::KeyInit;
-    let key = match hex::decode(appdata.paks[pak].1.trim_start_matches("0x")) {
-        Ok(key) if !appdata.paks[pak].1.is_empty() => aes::Aes256::new_from_slice(&key).ok(),
-        Ok(_) => None,
-        Err(_) => {
+    if placing.0.is_empty() {
+        return;
+    }
+    let (ready, waiting): (Vec<_>, Vec<_>) =
+        std::mem::take(&mut placing.0)
+            .into_iter()
+            .partition(|(_, mesh, _)| {
+                registry.meshes.contains_key(mesh)
+                    || !loading.tasks.iter().any(|(path, _)| path == mesh)
+            });
+    placing.0 = waiting;
+    let Some((map, path, export_names, import_names)) = &mut map.0 else {
+        return;
+    };
+    // anything queued for a map which has since been closed is dropped
+    for (_, mesh, at) in ready.into_iter().filter(|(placed, ..)| placed == path) {
+        let Some((handle, material)) = registry.meshes.get(&mesh) else {
             notif.send(Notif {
-                message: "aes key is invalid hex".into(),
-                kind: Warning,
+                message: format!("{mesh} isn't a static mesh"),
+                kind: Error,
+            });
+            continue;
+        };
+        // the new actor's exports are built from an existing one so its layout matches the map
+        let templates: Vec<_> = actor::get_actors(map)
+            .into_iter()
+            .filter_map(|i| actor::Actor::new(map, i).ok())
+            .filter(|(path, actor)| path.is_some() && actor.class == "StaticMeshActor")
+            .map(|(_, actor)| actor)
+            .collect();
+        // attached templates would need their location made relative to the parent
+        let Some(template) = templates
+            .iter()
+            .find(|template| template.attach_parent(map).is_none())
+            .or(templates.first())
+        else {
+            notif.send(Notif {
+                message: "the map needs a StaticMeshActor to build new ones from".into(),
+                kind: Error,
             });
-            None
+            continue;
+        };
+        let len = map.asset_data.exports.len();
+        template.spawn(map, &mesh, export_names, import_names);
+        let new =
+            match actor::Actor::new(map, unreal_asset::types::PackageIndex::new(len as i32 + 1)) {
+                Ok((_, new)) => new,
+                Err(e) => {
+                    notif.send(Notif {
+                        message: e.to_string(),
+                        kind: Error,
+                    });
+                    continue;
+                }
+            };
+        export_names[len] = new.name.clone();
+        let at = at.unwrap_or_else(|| {
+            let camera = camera.single();
+            camera.translation() + camera.forward() * 10.0
+        });
+        new.add_location(map, at.as_dvec3() + origin.0 - new.location(map));
+        for entity in selected.iter() {
+            picking::deselect(entity, &mut commands, &mut cubes, &consts);
         }
-    };
-    #[cfg(target_os = "windows")]
-    #[link(name = "oo2core_win64", kind = "static")]
-    extern "C" {
-        fn OodleLZ_Decompress(
-            compBuf: *const u8,
-            compBufSize: usize,
-            rawBuf: *mut u8,
-            rawLen: usize,
-            fuzzSafe: u32,
-            checkCRC: u32,
-            verbosity: u32,
-            decBufBase: u64,
-            decBufSize: usize,
-            fpCallback: u64,
-            callbackUserData: u64,
-            decoderMemory: *mut u8,
-            decoderMemorySize: usize,
-            threadPhase: u32,
-        ) -> i32;
+        notif.send(Notif {
+            message: format!("{} placed", new.name),
+            kind: Success,
+        });
+        commands.spawn((
+            actor::SelectedBundle::default(),
+            MaterialMeshBundle {
+                mesh: handle.clone(),
+                material: material
+                    .as_ref()
+                    .and_then(|mat| registry.mats.get(mat).cloned())
+                    .unwrap_or(consts.grid.clone_weak()),
+                transform: new.transform(map, origin.0),
+                ..default()
+            },
+            bevy_mod_raycast::deferred::RaycastMesh::<()>::default(),
+            new,
+        ));
     }
-    let path = &appdata.paks[pak].0;
-    let Ok(files) = std::fs::read_dir(path) else {
+}
+
+pub fn replace_mesh(
+    trigger: Trigger<triggers::ReplaceMesh>,
+    mut notif: EventWriter<Notif>,
+    mut commands: Commands,
+    mut map: NonSendMut<Map>,
+    appdata: Res<AppData>,
+    vfs: Res<vfs::Vfs>,
+    registry: Res<Registry>,
+    mut loading: ResMut<Loading>,
+    consts: Res<Constants>,
+    mut selected: Query<
+        (Entity, &mut actor::Actor, Has<Handle<wire::Wire>>),
+        With<actor::Selected>,
+    >,
+) {
+    let Some((map, _, _, import_names)) = &mut map.0 else {
         return;
     };
-    paks.1 = path.into();
-    // use std::io::Write;
-    // let mut log = std::fs::File::create("paks.log").unwrap();
-    paks.2 = files
-        .filter_map(Result::ok)
-        .map(|dir| dir.path())
-        .filter_map(|path| {
-            let mut pak_file = std::io::BufReader::new(std::fs::File::open(path.clone()).ok()?);
-            let mut pak = repak::PakBuilder::new();
-            if let Some(key) = key.as_ref() {
-                pak = pak.key(key.clone());
-            }
-            #[cfg(target_os = "windows")]
-            {
-                pak = pak.oodle(|| {
-                    Ok(|comp_buf, raw_buf| unsafe {
-                        OodleLZ_Decompress(
-                            comp_buf.as_ptr(),
-                            comp_buf.len(),
-                            raw_buf.as_mut_ptr(),
-                            raw_buf.len(),
-                            1,
-                            1,
-                            0,
-                            0,
-                            0,
-                            0,
-                            0,
-                            std::ptr::null_mut(),
-                            0,
-                            3,
-                        )
-                    })
-                });
-            }
-            let pak = pak.reader(&mut pak_file).ok()?;
-            // let _ = writeln!(
-            //     &mut log,
-            //     "{path:?}\n{}\n{:?}",
-            //     pak.mount_point(),
-            //     pak.files()
-            // );
-            Some((path, pak))
-        })
-        .collect();
-    // obtain game name
-    for (_, pak) in paks.2.iter() {
-        let mut split = pak.mount_point().split('/').peekable();
-        while let Some((game, content)) = split.next().zip(split.peek()) {
-            if game != "Engine" && content == &"Content" {
-                paks.0 = game.into();
-                return;
-            }
+    if selected.is_empty() {
+        notif.send(Notif {
+            message: "no actors to replace the mesh of".into(),
+            kind: Warning,
+        });
+        return;
+    }
+    // accept object paths and file names too
+    let mesh = trigger.event().0.trim();
+    let mesh = mesh.split_once('.').map_or(mesh, |(package, _)| package);
+    if asset::class(&vfs, mesh, appdata.version()).as_deref() != Some("StaticMesh") {
+        notif.send(Notif {
+            message: format!("{mesh} isn't a static mesh"),
+            kind: Error,
+        });
+        return;
+    }
+    let mut replaced = 0;
+    for (entity, mut actor, placeholder) in selected.iter_mut() {
+        if actor.replace_mesh(map, mesh, import_names) == 0 {
+            notif.send(Notif {
+                message: match actor::unversioned(map) {
+                    true => format!(
+                        "{} has no static mesh set and one can't be added in unversioned packages",
+                        actor.name
+                    ),
+                    false => format!("{} has no static mesh component", actor.name),
+                },
+                kind: Warning,
+            });
+            continue;
         }
-        for entry in pak.files() {
-            let mut split = entry.split('/').take(2);
-            if let Some((game, content)) = split.next().zip(split.next()) {
-                if game != "Engine" && content == "Content" {
-                    paks.0 = game.into();
-                    return;
+        replaced += 1;
+        actor.mesh = Some(mesh.to_string());
+        let mut entity = commands.entity(entity);
+        match registry.meshes.get(mesh) {
+            Some((handle, material)) => {
+                let material = material
+                    .as_ref()
+                    .and_then(|mat| registry.mats.get(mat).cloned())
+                    .unwrap_or(consts.grid.clone_weak());
+                entity
+                    .remove::<actor::Pending>()
+                    .insert((handle.clone(), material));
+                if placeholder {
+                    entity
+                        .despawn_descendants()
+                        .remove::<(Handle<wire::Wire>, bevy::pbr::wireframe::NoWireframe)>()
+                        .insert((
+                            bevy_mod_raycast::deferred::RaycastMesh::<()>::default(),
+                            actor::SelectedBundle::default(),
+                        ));
                 }
             }
-        }
-    }
-    if let Ok(dir) = std::fs::read_dir(path) {
-        for dir in dir.filter_map(Result::ok) {
-            if !dir.file_type().is_ok_and(|t| t.is_dir()) {
-                continue;
-            }
-            let name = dir.file_name().to_string_lossy().into();
-            if name == "Engine" {
-                continue;
-            }
-            if dir.path().join("Content").exists() {
-                paks.0 = name;
+            // the old mesh stays up until the new one streams in
+            None => {
+                entity.insert(actor::Pending(mesh.to_string()));
+                dialog::queue(
+                    &mut loading,
+                    &vfs,
+                    appdata
+                        .profile()
+                        .cache
+                        .then(|| cache::Cache::new(appdata.profile()))
+                        .flatten(),
+                    mesh.to_string(),
+                    appdata.version(),
+                    appdata.textures,
+                );
             }
         }
     }
+    if replaced != 0 {
+        notif.send(Notif {
+            message: format!("replaced the mesh of {replaced} actors"),
+            kind: Success,
+        });
+    }
 }
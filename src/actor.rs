@@ -10,10 +10,19 @@ use unreal_asset::{
 
 mod delete;
 mod duplicate;
+mod package;
+mod references;
+mod replace;
+mod spawn;
 mod transform;
 mod transplant;
 mod ui;
 
+pub use package::{remove_import, used_names};
+pub use replace::unversioned;
+use replace::{mesh_import, repoint};
+pub use ui::{index_name, show_export, show_many};
+
 pub const LOCATION: &str = "RelativeLocation";
 pub const ROTATION: &str = "RelativeRotation";
 pub const SCALE: &str = "RelativeScale3D";
@@ -46,6 +55,14 @@ pub struct Selected;
 #[derive(bevy::prelude::Component)]
 pub struct Matched;
 
+/// the mesh a placeholder is waiting on
+#[derive(bevy::prelude::Component)]
+pub struct Pending(pub String);
+
+/// the component export shown in the details panel instead of the actor
+#[derive(bevy::prelude::Component)]
+pub struct Inspected(pub usize);
+
 #[derive(bevy::prelude::Component)]
 pub struct Actor {
     pub export: usize,
@@ -53,6 +70,7 @@ pub struct Actor {
     pub name: String,
     pub display: String,
     pub class: String,
+    pub mesh: Option<String>,
 }
 
 impl Actor {
@@ -82,11 +100,14 @@ impl Actor {
         let name = match asset.get_engine_version()
             >= unreal_asset::engine_version::EngineVersion::VER_UE5_1
         {
-            true => match norm.extras[8..12]
-                .try_into()
-                .ok()
-                .map(|i| i32::from_le_bytes(i) as usize)
-                .and_then(|len| String::from_utf8(norm.extras[12..12 + len].to_vec()).ok())
+            // the label is still stored the same way in later versions but bounds are checked for modded layouts
+            true => match norm
+                .extras
+                .get(8..12)
+                .and_then(|len| len.try_into().ok())
+                .map(|len| i32::from_le_bytes(len).max(0) as usize)
+                .and_then(|len| norm.extras.get(12..12 + len))
+                .and_then(|label| String::from_utf8(label.to_vec()).ok())
             {
                 Some(name) if !name.chars().all(char::is_whitespace) => name,
                 _ => norm.base_export.object_name.get_owned_content(),
@@ -122,13 +143,14 @@ impl Actor {
                 // of course this wouldn't be able to be detected if all transforms were left default
                 LOCATION | ROTATION | SCALE => {
                     return Ok((
-                        path,
+                        path.clone(),
                         Self {
                             export,
                             transform: export,
                             name,
                             display,
                             class,
+                            mesh: path,
                         },
                     ))
                 }
@@ -136,13 +158,14 @@ impl Actor {
                     if let Property::ObjectProperty(obj) = prop {
                         if obj.value.is_export() {
                             return Ok((
-                                path,
+                                path.clone(),
                                 Self {
                                     export,
                                     transform: obj.value.index as usize - 1,
                                     name,
                                     display,
                                     class,
+                                    mesh: path,
                                 },
                             ));
                         }
@@ -158,32 +181,44 @@ impl Actor {
         })
     }
 
-    /// gets all exports related to the given actor
-    fn get_actor_exports(&self, asset: &super::Asset, offset: usize) -> Vec<super::Export> {
-        let level = asset
+    /// gets references to all of the actor's components (excluding child actors)
+    pub fn components(&self, asset: &Asset) -> Vec<PackageIndex> {
+        let Some(level) = asset
             .asset_data
             .exports
             .iter()
-            .find_map(|ex| unreal_asset::cast!(Export, LevelExport, ex))
-            .unwrap();
-        // get references to all the actor's children
-        let mut child_indexes: Vec<PackageIndex> = asset.asset_data.exports[self.export]
+            .find_map(|ex| cast!(Export, LevelExport, ex))
+        else {
+            return Vec::new();
+        };
+        asset.asset_data.exports[self.export]
             .get_base_export()
             .create_before_serialization_dependencies
             .iter()
-            .filter(|dep| dep.is_export())
+            .filter(|dep| dep.is_export() && !level.actors.contains(dep))
             // dw PackageIndex is just a wrapper around i32 which is cloned by default anyway
             .cloned()
-            .collect();
-        let actors: Vec<_> = child_indexes
-            .iter()
-            .enumerate()
-            .rev()
-            .filter_map(|(i, child)| level.actors.contains(child).then_some(i))
-            .collect();
-        for i in actors {
-            child_indexes.remove(i);
-        }
+            .collect()
+    }
+
+    /// gets the export index of the root component
+    pub fn root_component(&self) -> usize {
+        self.transform
+    }
+
+    /// gets the export index of the actor this is attached to
+    pub fn attach_parent(&self, asset: &Asset) -> Option<usize> {
+        // the attached component is owned by the parent actor
+        let outer = asset.asset_data.exports[attached_to(asset, self.transform)?]
+            .get_base_export()
+            .outer_index;
+        outer.is_export().then(|| outer.index as usize - 1)
+    }
+
+    /// gets all exports related to the given actor
+    fn get_actor_exports(&self, asset: &super::Asset, offset: usize) -> Vec<super::Export> {
+        // get references to all the actor's children
+        let mut child_indexes = self.components(asset);
         // add the top-level actor reference
         child_indexes.insert(0, self.index());
 
@@ -228,6 +263,19 @@ pub fn get_actors(asset: &Asset) -> Vec<PackageIndex> {
     }
 }
 
+/// gets the export index of the component the given component is attached to
+pub fn attached_to(asset: &Asset, component: usize) -> Option<usize> {
+    let parent = asset.asset_data.exports[component]
+        .get_normal_export()?
+        .properties
+        .iter()
+        .find_map(|prop| {
+            cast!(Property, ObjectProperty, prop).filter(|_| prop.get_name() == "AttachParent")
+        })?
+        .value;
+    parent.is_export().then(|| parent.index as usize - 1)
+}
+
 /// creates and assigns a unique name
 fn give_unique_name(orig: &mut FName, asset: &mut Asset) {
     // for the cases where the number is unnecessary
@@ -285,6 +333,19 @@ fn on_props(prop: &mut Property, func: &mut impl FnMut(&mut Property)) {
             for val in map.value.values_mut() {
                 on_props(val, func);
             }
+            // keys can't be changed in place so the map is rebuilt if any of them change
+            let mut keys: Vec<_> = map.value.keys().cloned().collect();
+            for key in keys.iter_mut() {
+                on_props(key, func);
+            }
+            if keys.iter().ne(map.value.keys()) {
+                let values: Vec<_> = map.value.values().cloned().collect();
+                let mut rebuilt = unreal_asset::containers::IndexedMap::new();
+                for (key, value) in keys.into_iter().zip(values) {
+                    rebuilt.insert(key, value);
+                }
+                map.value = rebuilt;
+            }
         }
         Property::SetProperty(set) => {
             for entry in set.value.value.iter_mut() {
@@ -303,6 +364,69 @@ fn on_props(prop: &mut Property, func: &mut impl FnMut(&mut Property)) {
     }
 }
 
+/// a read-only on_props which keeps track of the path to each property
+fn props(prop: &Property, path: String, func: &mut impl FnMut(String, &Property)) {
+    match prop {
+        Property::ArrayProperty(arr) => {
+            for (i, entry) in arr.value.iter().enumerate() {
+                props(entry, format!("{path}[{i}]"), func);
+            }
+        }
+        Property::MapProperty(map) => {
+            for (i, (key, val)) in map.value.keys().zip(map.value.values()).enumerate() {
+                props(key, format!("{path}[{i}].key"), func);
+                props(val, format!("{path}[{i}].value"), func);
+            }
+        }
+        Property::SetProperty(set) => {
+            for (i, entry) in set.value.value.iter().enumerate() {
+                props(entry, format!("{path}[{i}]"), func);
+            }
+            for (i, entry) in set.removed_items.value.iter().enumerate() {
+                props(entry, format!("{path}.removed[{i}]"), func);
+            }
+        }
+        Property::StructProperty(struc) => {
+            for entry in struc.value.iter() {
+                props(
+                    entry,
+                    format!("{path}.{}", entry.get_name().get_owned_content()),
+                    func,
+                );
+            }
+        }
+        prop => func(path, prop),
+    }
+}
+
+/// a read-only on_prop_refs which keeps track of where each reference is
+fn prop_refs(prop: &Property, func: &mut impl FnMut(String, &PackageIndex)) {
+    props(
+        prop,
+        prop.get_name().get_owned_content(),
+        &mut |path, prop| match prop {
+            Property::ObjectProperty(obj) => func(path, &obj.value),
+            Property::DelegateProperty(del) => func(path, &del.value.object),
+            Property::MulticastDelegateProperty(del) => {
+                for (i, delegate) in del.value.iter().enumerate() {
+                    func(format!("{path}[{i}]"), &delegate.object)
+                }
+            }
+            Property::MulticastSparseDelegateProperty(del) => {
+                for (i, delegate) in del.value.iter().enumerate() {
+                    func(format!("{path}[{i}]"), &delegate.object)
+                }
+            }
+            Property::MulticastInlineDelegateProperty(del) => {
+                for (i, delegate) in del.value.iter().enumerate() {
+                    func(format!("{path}[{i}]"), &delegate.object)
+                }
+            }
+            _ => (),
+        },
+    )
+}
+
 /// on any possible references stashed away in properties
 fn on_prop_refs(prop: &mut Property, func: &mut impl FnMut(&mut PackageIndex)) {
     on_props(prop, &mut |prop| match prop {
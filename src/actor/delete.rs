@@ -1,29 +1,38 @@
 use super::*;
 
 impl Actor {
-    /// delete an actor from a map
-    pub fn delete(&self, map: &mut Asset) {
-        let val = self.index();
-        if let Some(level) = map
-            .asset_data
-            .exports
-            .iter_mut()
-            .find_map(|ex| cast!(Export, LevelExport, ex))
-        {
-            level
-                .actors
-                .remove(level.actors.iter().position(|i| i == &val).unwrap());
-            if let Some(pos) = level
-                .get_base_export()
-                .create_before_serialization_dependencies
-                .iter()
-                .position(|i| i == &val)
-            {
-                level
-                    .get_base_export_mut()
-                    .create_before_serialization_dependencies
-                    .remove(pos);
+    /// delete an actor from a map nulling references to it and returning how many there were
+    pub fn delete(&self, map: &mut Asset) -> usize {
+        let own = self.owned(map);
+        let mut nulled = 0;
+        for (i, export) in map.asset_data.exports.iter_mut().enumerate() {
+            if own.contains(&PackageIndex::new(i as i32 + 1)) {
+                continue;
+            }
+            if let Some(level) = cast!(Export, LevelExport, export) {
+                level.actors.retain(|i| !own.contains(i));
+            }
+            if let Some(norm) = export.get_normal_export_mut() {
+                for prop in norm.properties.iter_mut() {
+                    on_prop_refs(prop, &mut |i| {
+                        if own.contains(i) {
+                            *i = PackageIndex::new(0);
+                            nulled += 1;
+                        }
+                    });
+                }
+            }
+            // dependencies are dropped rather than nulled since they're only ordering hints
+            let base = export.get_base_export_mut();
+            for deps in [
+                &mut base.create_before_create_dependencies,
+                &mut base.create_before_serialization_dependencies,
+                &mut base.serialization_before_create_dependencies,
+                &mut base.serialization_before_serialization_dependencies,
+            ] {
+                deps.retain(|i| !own.contains(i));
             }
         }
+        nulled
     }
 }
@@ -0,0 +1,142 @@
+use super::*;
+use std::collections::BTreeSet;
+use unreal_asset::{properties::int_property::BytePropertyValue, types::fname::ToSerializedName};
+
+/// removes an import nulling references to it and shifting the ones after it
+///
+/// refuses with how many exports are instances of it or inside it since nulling those breaks them
+pub fn remove_import(map: &mut Asset, import: usize) -> Result<usize, usize> {
+    let removed = -(import as i32) - 1;
+    let users = map
+        .asset_data
+        .exports
+        .iter()
+        .map(ExportBaseTrait::get_base_export)
+        .filter(|base| base.class_index.index == removed || base.outer_index.index == removed)
+        .count();
+    if users != 0 {
+        return Err(users);
+    }
+    let mut nulled = 0;
+    let mut shift = |index: &mut PackageIndex| match index.index {
+        i if i == removed => {
+            index.index = 0;
+            nulled += 1;
+        }
+        i if i < removed => index.index += 1,
+        _ => (),
+    };
+    map.imports.remove(import);
+    for import in map.imports.iter_mut() {
+        shift(&mut import.outer_index);
+    }
+    // references tucked away in unparsed extras aren't renumbered
+    for export in map.asset_data.exports.iter_mut() {
+        if let Some(norm) = export.get_normal_export_mut() {
+            for prop in norm.properties.iter_mut() {
+                on_prop_refs(prop, &mut shift);
+            }
+        }
+        let base = export.get_base_export_mut();
+        for index in [
+            &mut base.class_index,
+            &mut base.super_index,
+            &mut base.template_index,
+            &mut base.outer_index,
+        ] {
+            shift(index);
+        }
+        for deps in [
+            &mut base.create_before_create_dependencies,
+            &mut base.create_before_serialization_dependencies,
+            &mut base.serialization_before_create_dependencies,
+            &mut base.serialization_before_serialization_dependencies,
+        ] {
+            deps.iter_mut().for_each(&mut shift);
+            deps.retain(|i| i.index != 0);
+        }
+    }
+    Ok(nulled)
+}
+
+/// the names stove can see being used by imports, exports and properties
+pub fn used_names(map: &Asset) -> BTreeSet<String> {
+    let mut names = BTreeSet::from(["None".to_string()]);
+    for import in map.imports.iter() {
+        for name in [
+            &import.class_package,
+            &import.class_name,
+            &import.object_name,
+        ] {
+            names.insert(name.get_owned_content());
+        }
+    }
+    for export in map.asset_data.exports.iter() {
+        names.insert(export.get_base_export().object_name.get_owned_content());
+        if let Some(norm) = export.get_normal_export() {
+            for prop in norm.properties.iter() {
+                prop_names(prop, &mut names);
+            }
+        }
+    }
+    names
+}
+
+fn prop_names(prop: &Property, names: &mut BTreeSet<String>) {
+    names.insert(prop.get_name().get_owned_content());
+    names.insert(prop.to_serialized_name());
+    let mut add = |name: &FName| {
+        names.insert(name.get_owned_content());
+    };
+    match prop {
+        Property::StructProperty(struc) => {
+            if let Some(ty) = &struc.struct_type {
+                add(ty);
+            }
+            for entry in struc.value.iter() {
+                prop_names(entry, names);
+            }
+        }
+        Property::ArrayProperty(arr) => {
+            for entry in arr.value.iter() {
+                prop_names(entry, names);
+            }
+        }
+        Property::SetProperty(set) => {
+            for entry in set.value.value.iter() {
+                prop_names(entry, names);
+            }
+        }
+        Property::MapProperty(map) => {
+            add(&map.key_type);
+            add(&map.value_type);
+            for (key, value) in map.value.keys().zip(map.value.values()) {
+                prop_names(key, names);
+                prop_names(value, names);
+            }
+        }
+        Property::NameProperty(name) => add(&name.value),
+        Property::EnumProperty(enm) => {
+            for name in [&enm.enum_type, &enm.value].into_iter().flatten() {
+                add(name);
+            }
+        }
+        Property::ByteProperty(byte) => {
+            if let Some(ty) = &byte.enum_type {
+                add(ty);
+            }
+            if let BytePropertyValue::FName(name) = &byte.value {
+                add(name);
+            }
+        }
+        Property::SoftObjectProperty(obj) => {
+            add(&obj.value.asset_path.asset_name);
+            if let Some(package) = &obj.value.asset_path.package_name {
+                add(package);
+            }
+        }
+        Property::GameplayTagContainerProperty(tags) => tags.value.iter().for_each(add),
+        Property::SmartNameProperty(name) => add(&name.display_name),
+        _ => (),
+    }
+}
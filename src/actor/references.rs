@@ -0,0 +1,74 @@
+use super::*;
+
+impl Actor {
+    /// the actor, its components and anything nested inside them
+    pub fn owned(&self, map: &Asset) -> Vec<PackageIndex> {
+        let mut own: Vec<_> = std::iter::once(self.index())
+            .chain(self.components(map))
+            .collect();
+        loop {
+            let len = own.len();
+            for (i, export) in map.asset_data.exports.iter().enumerate() {
+                let index = PackageIndex::new(i as i32 + 1);
+                if !own.contains(&index) && own.contains(&export.get_base_export().outer_index) {
+                    own.push(index);
+                }
+            }
+            if own.len() == len {
+                break own;
+            }
+        }
+    }
+
+    /// every export outside this actor which references it or its components with the path to the reference
+    pub fn referenced_by(&self, map: &Asset) -> Vec<(usize, String)> {
+        let own = self.owned(map);
+        let mut refs = Vec::new();
+        for (i, export) in map.asset_data.exports.iter().enumerate() {
+            // the level only lists actors so isn't a real dependant
+            if own.contains(&PackageIndex::new(i as i32 + 1))
+                || cast!(Export, LevelExport, export).is_some()
+            {
+                continue;
+            }
+            export_refs(export, &mut |path, index| {
+                if own.contains(index) {
+                    refs.push((i, path))
+                }
+            });
+        }
+        refs
+    }
+}
+
+/// the references delete clears, being those in properties and dependency lists
+fn export_refs(export: &Export, func: &mut impl FnMut(String, &PackageIndex)) {
+    if let Some(norm) = export.get_normal_export() {
+        for prop in norm.properties.iter() {
+            prop_refs(prop, func);
+        }
+    }
+    let export = export.get_base_export();
+    for (path, deps) in [
+        (
+            "create before create",
+            &export.create_before_create_dependencies,
+        ),
+        (
+            "create before serialization",
+            &export.create_before_serialization_dependencies,
+        ),
+        (
+            "serialization before create",
+            &export.serialization_before_create_dependencies,
+        ),
+        (
+            "serialization before serialization",
+            &export.serialization_before_serialization_dependencies,
+        ),
+    ] {
+        for dep in deps {
+            func(path.to_string(), dep)
+        }
+    }
+}
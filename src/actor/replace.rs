@@ -0,0 +1,301 @@
+use super::*;
+use unreal_asset::{flags::EPackageFlags, properties::object_property::ObjectProperty, Import};
+
+impl Actor {
+    /// points the actor's static mesh components at another mesh returning how many changed
+    pub fn replace_mesh(
+        &self,
+        asset: &mut Asset,
+        mesh: &str,
+        import_names: &mut Vec<String>,
+    ) -> usize {
+        let name = asset.add_fname("StaticMesh");
+        // added properties would need their place in the mappings which stove doesn't work out
+        let unversioned = unversioned(asset);
+        // components left on the default mesh don't store the property at all
+        let changing: Vec<_> = self
+            .components(asset)
+            .into_iter()
+            .filter_map(|component| {
+                let ex = asset.get_export(component)?;
+                let old = current(ex);
+                let missing = asset
+                    .get_import(ex.get_base_export().class_index)
+                    .is_some_and(|class| !unversioned && class.object_name == "StaticMeshComponent")
+                    .then(|| name.clone());
+                (old.is_some() || missing.is_some()).then_some((component, old, missing))
+            })
+            .collect();
+        // only add the imports once something's going to use them
+        if changing.is_empty() {
+            return 0;
+        }
+        let object = mesh_import(asset, mesh, import_names);
+        let mut replaced = 0;
+        let mut old = Vec::new();
+        for (component, prev, missing) in changing {
+            if repoint(
+                &mut asset.asset_data.exports[component.index as usize - 1],
+                object,
+                missing,
+            ) {
+                replaced += 1;
+                old.extend(prev.filter(|prev| prev.is_import() && *prev != object));
+            }
+        }
+        // otherwise the game still loads the old package
+        prune(asset, import_names, old);
+        replaced
+    }
+}
+
+pub fn unversioned(asset: &Asset) -> bool {
+    asset
+        .asset_data
+        .summary
+        .package_flags
+        .contains(EPackageFlags::PKG_UNVERSIONED_PROPERTIES)
+}
+
+/// the mesh a component currently shows
+fn current(export: &super::Export) -> Option<PackageIndex> {
+    export
+        .get_normal_export()?
+        .properties
+        .iter()
+        .find_map(|prop| {
+            cast!(Property, ObjectProperty, prop).filter(|_| prop.get_name() == "StaticMesh")
+        })
+        .map(|prop| prop.value)
+}
+
+/// removes the imports nothing points at anymore along with their packages
+fn prune(asset: &mut Asset, import_names: &mut Vec<String>, mut imports: Vec<PackageIndex>) {
+    imports.sort_by_key(|import| import.index);
+    imports.dedup();
+    while let Some(i) = imports.iter().position(|import| !used(asset, *import)) {
+        let removed = imports.swap_remove(i);
+        let index = removed.index.unsigned_abs() as usize - 1;
+        let Some(outer) = asset.imports.get(index).map(|import| import.outer_index) else {
+            continue;
+        };
+        if outer.is_import() && !imports.contains(&outer) {
+            imports.push(outer);
+        }
+        if remove_import(asset, index).is_err() {
+            continue;
+        }
+        import_names.remove(index);
+        for import in imports
+            .iter_mut()
+            .filter(|import| import.index < removed.index)
+        {
+            import.index += 1;
+        }
+    }
+}
+
+/// whether any import or export still references an import
+fn used(asset: &Asset, import: PackageIndex) -> bool {
+    if asset
+        .imports
+        .iter()
+        .any(|other| other.outer_index == import)
+    {
+        return true;
+    }
+    asset.asset_data.exports.iter().any(|export| {
+        let mut found = false;
+        if let Some(norm) = export.get_normal_export() {
+            for prop in norm.properties.iter() {
+                prop_refs(prop, &mut |_, index| found |= *index == import);
+            }
+        }
+        let base = export.get_base_export();
+        found
+            || [
+                base.class_index,
+                base.super_index,
+                base.template_index,
+                base.outer_index,
+            ]
+            .contains(&import)
+            || [
+                &base.create_before_create_dependencies,
+                &base.create_before_serialization_dependencies,
+                &base.serialization_before_create_dependencies,
+                &base.serialization_before_serialization_dependencies,
+            ]
+            .into_iter()
+            .any(|deps| deps.contains(&import))
+    })
+}
+
+/// finds or adds the package and object imports a mesh is referenced through
+pub fn mesh_import(asset: &mut Asset, mesh: &str, import_names: &mut Vec<String>) -> PackageIndex {
+    let package = import(
+        asset,
+        import_names,
+        ("/Script/CoreUObject", "Package", mesh),
+        PackageIndex::new(0),
+    );
+    import(
+        asset,
+        import_names,
+        (
+            "/Script/Engine",
+            "StaticMesh",
+            mesh.rsplit('/').next().unwrap_or(mesh),
+        ),
+        package,
+    )
+}
+
+/// swaps the mesh a component shows adding the property under the given name if it's missing which only versioned packages can do
+pub fn repoint(export: &mut super::Export, object: PackageIndex, missing: Option<FName>) -> bool {
+    let Some(norm) = export.get_normal_export_mut() else {
+        return false;
+    };
+    match norm.properties.iter_mut().find_map(|prop| {
+        let name = prop.get_name();
+        cast!(Property, ObjectProperty, prop).filter(|_| name == "StaticMesh")
+    }) {
+        Some(prop) => {
+            let old = std::mem::replace(&mut prop.value, object);
+            let base = &mut norm.base_export;
+            for dep in base
+                .create_before_create_dependencies
+                .iter_mut()
+                .chain(base.create_before_serialization_dependencies.iter_mut())
+                .chain(base.serialization_before_create_dependencies.iter_mut())
+                .chain(
+                    base.serialization_before_serialization_dependencies
+                        .iter_mut(),
+                )
+                .filter(|dep| **dep == old)
+            {
+                *dep = object;
+            }
+            // the mesh has to exist before the component can be read even if the old one wasn't listed
+            if !base
+                .create_before_serialization_dependencies
+                .contains(&object)
+            {
+                base.create_before_serialization_dependencies.push(object);
+            }
+        }
+        None => {
+            let Some(name) = missing else {
+                return false;
+            };
+            // tagged properties carry their own type so the ancestry only matters to unversioned packages which never get here
+            norm.properties
+                .push(Property::ObjectProperty(ObjectProperty {
+                    name,
+                    ancestry: unreal_asset::unversioned::ancestry::Ancestry {
+                        ancestry: Vec::new(),
+                    },
+                    property_guid: None,
+                    duplication_index: 0,
+                    value: object,
+                }));
+            // the mesh has to exist before the component can be read
+            norm.base_export
+                .create_before_serialization_dependencies
+                .push(object);
+        }
+    }
+    // the materials were picked for the old mesh
+    norm.properties
+        .retain(|prop| prop.get_name() != "OverrideMaterials");
+    true
+}
+
+/// finds an import or adds it along with its names
+fn import(
+    asset: &mut Asset,
+    import_names: &mut Vec<String>,
+    (class_package, class_name, object_name): (&str, &str, &str),
+    outer: PackageIndex,
+) -> PackageIndex {
+    if let Some(i) = asset.imports.iter().position(|import| {
+        import.class_package == class_package
+            && import.class_name == class_name
+            && import.object_name == object_name
+            && import.outer_index == outer
+    }) {
+        return PackageIndex::new(-(i as i32) - 1);
+    }
+    let import = Import::new(
+        asset.add_fname(class_package),
+        asset.add_fname(class_name),
+        outer,
+        asset.add_fname(object_name),
+        false,
+    );
+    asset.imports.push(import);
+    import_names.push(object_name.to_string());
+    PackageIndex::new(-(asset.imports.len() as i32))
+}
+
+#[test]
+fn roundtrip() -> Result<(), unreal_asset::error::Error> {
+    use std::io::Cursor;
+    let write = |asset: &mut Asset| -> Result<Asset, unreal_asset::error::Error> {
+        let (mut data, mut exp) = (Cursor::new(Vec::new()), Cursor::new(Vec::new()));
+        asset.rebuild_name_map();
+        asset.write_data(&mut data, Some(&mut exp))?;
+        Asset::new(
+            crate::Wrapper::Bytes(Cursor::new(data.into_inner())),
+            Some(crate::Wrapper::Bytes(Cursor::new(exp.into_inner()))),
+            asset.get_engine_version(),
+            None,
+        )
+    };
+    let mut asset = Asset::new(
+        crate::Wrapper::Bytes(Cursor::new(
+            include_bytes!("../extras/tests/SM_Cybercity_Hook_End.uasset").to_vec(),
+        )),
+        Some(crate::Wrapper::Bytes(Cursor::new(
+            include_bytes!("../extras/tests/SM_Cybercity_Hook_End.uexp").to_vec(),
+        ))),
+        unreal_asset::engine_version::EngineVersion::VER_UE4_27,
+        None,
+    )?;
+    let imports = asset.imports.len();
+    let mut import_names: Vec<_> = asset
+        .imports
+        .iter()
+        .map(|import| import.object_name.get_owned_content())
+        .collect();
+    // the package's main export stands in for a component which doesn't store its mesh yet
+    let main = asset
+        .asset_data
+        .exports
+        .iter()
+        .position(|ex| ex.get_base_export().outer_index.index == 0)
+        .unwrap();
+    let rock = mesh_import(&mut asset, "/Game/Meshes/SM_Rock", &mut import_names);
+    let name = asset.add_fname("StaticMesh");
+    assert!(repoint(
+        &mut asset.asset_data.exports[main],
+        rock,
+        Some(name)
+    ));
+    let mut asset = write(&mut asset)?;
+    assert_eq!(asset.imports.len(), imports + 2);
+    assert_eq!(current(&asset.asset_data.exports[main]), Some(rock));
+    // swapping it again leaves the first mesh's imports unused
+    let tree = mesh_import(&mut asset, "/Game/Meshes/SM_Tree", &mut import_names);
+    assert!(repoint(&mut asset.asset_data.exports[main], tree, None));
+    prune(&mut asset, &mut import_names, vec![rock]);
+    let asset = write(&mut asset)?;
+    assert_eq!(asset.imports.len(), imports + 2);
+    assert_eq!(import_names.len(), asset.imports.len());
+    let mesh = current(&asset.asset_data.exports[main]).and_then(|mesh| asset.get_import(mesh));
+    assert_eq!(
+        mesh.map(|mesh| mesh.object_name.get_owned_content()),
+        Some("SM_Tree".into())
+    );
+    Ok(())
+}
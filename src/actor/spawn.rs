@@ -0,0 +1,136 @@
+use super::*;
+
+impl Actor {
+    /// adds a new static mesh actor built from this one showing a different mesh
+    pub fn spawn(
+        &self,
+        asset: &mut Asset,
+        mesh: &str,
+        export_names: &mut Vec<String>,
+        import_names: &mut Vec<String>,
+    ) {
+        let len = asset.asset_data.exports.len();
+        let mut children = self.get_actor_exports(asset, len);
+
+        // name it after the class rather than whatever actor it was built from
+        let mut name = asset.add_fname("StaticMeshActor");
+        give_unique_name(&mut name, asset);
+        let label = name.get_owned_content();
+        children[0].get_base_export_mut().object_name = name;
+        if let Some(norm) = children[0].get_normal_export_mut() {
+            norm.properties.retain(|prop| {
+                !matches!(
+                    prop.get_name().get_owned_content().as_str(),
+                    "Tags" | "bHidden" | "bActorEnableCollision" | "Layers"
+                )
+            });
+            if asset.get_engine_version() >= unreal_asset::engine_version::EngineVersion::VER_UE5_1
+            {
+                relabel(&mut norm.extras, &label);
+            }
+        }
+        // whatever the template's mesh was set up with doesn't suit the new one
+        for norm in children[1..]
+            .iter_mut()
+            .filter_map(ExportNormalTrait::get_normal_export_mut)
+        {
+            norm.properties.retain(|prop| {
+                !matches!(
+                    prop.get_name().get_owned_content().as_str(),
+                    "OverrideMaterials"
+                        | "BodyInstance"
+                        | "bUseDefaultCollision"
+                        | "bHiddenInGame"
+                        | "bVisible"
+                        | "bOverrideLightMapRes"
+                        | "OverriddenLightMapRes"
+                        | "LODData"
+                )
+            });
+            if norm
+                .properties
+                .iter()
+                .any(|prop| prop.get_name() == "StaticMesh")
+            {
+                strip_lods(&mut norm.extras);
+            }
+        }
+        // the copy starts out detached with no rotation or scale
+        let root = std::iter::once(self.index())
+            .chain(self.components(asset))
+            .position(|index| index.index as usize - 1 == self.transform);
+        if let Some(norm) = root
+            .and_then(|root| children.get_mut(root))
+            .and_then(ExportNormalTrait::get_normal_export_mut)
+        {
+            norm.properties.retain(|prop| {
+                !matches!(
+                    prop.get_name().get_owned_content().as_str(),
+                    ROTATION | SCALE | "AttachParent" | "AttachSocketName" | "ComponentTags"
+                )
+            });
+        }
+
+        let object = mesh_import(asset, mesh, import_names);
+        for child in children[1..].iter_mut() {
+            repoint(child, object, None);
+        }
+
+        let actor_ref = PackageIndex::new(len as i32 + 1);
+        // add the actor to persistent level
+        if let Some(level) = asset
+            .asset_data
+            .exports
+            .iter_mut()
+            .find_map(|ex| cast!(Export, LevelExport, ex))
+        {
+            level.actors.push(actor_ref);
+            level
+                .get_base_export_mut()
+                .create_before_serialization_dependencies
+                .push(actor_ref);
+        }
+
+        export_names.extend(
+            children
+                .iter()
+                .map(|ex| ex.get_base_export().object_name.get_owned_content()),
+        );
+        asset.asset_data.exports.append(&mut children);
+    }
+}
+
+/// replaces the editor label which follows the actor's properties
+fn relabel(extras: &mut Vec<u8>, label: &str) {
+    let Some(len) = extras
+        .get(8..12)
+        .and_then(|len| len.try_into().ok())
+        .map(|len| i32::from_le_bytes(len).max(0) as usize)
+        .filter(|len| extras.len() >= 12 + len)
+    else {
+        return;
+    };
+    // labels are serialised with their null terminator
+    let mut new = label.as_bytes().to_vec();
+    new.push(0);
+    extras.splice(
+        8..12 + len,
+        (new.len() as i32).to_le_bytes().into_iter().chain(new),
+    );
+}
+
+/// empties the lod data after a mesh component's properties which holds painted vertex colours and lightmaps
+fn strip_lods(extras: &mut Vec<u8>) {
+    // the guid flag comes first then the lod count
+    let Some(count) = extras
+        .get(4..8)
+        .and_then(|count| count.try_into().ok())
+        .map(u32::from_le_bytes)
+    else {
+        return;
+    };
+    if count != 0 {
+        extras.truncate(4);
+        extras.extend(0u32.to_le_bytes());
+    }
+}
@@ -8,26 +8,12 @@ use unreal_asset::{
 };
 
 impl Actor {
-    pub fn location(&self, map: &Asset) -> bevy::math::Vec3 {
-        map.asset_data.exports[self.transform]
-            .get_normal_export()
-            .and_then(|norm| {
-                norm.properties.iter().rev().find_map(|prop| {
-                    if let Property::StructProperty(struc) = prop {
-                        if struc.name == LOCATION {
-                            return cast!(Property, VectorProperty, &struc.value[0]);
-                        }
-                    }
-                    None
-                })
-            })
-            .map(|pos| {
-                bevy::math::dvec3(pos.value.x.0, pos.value.z.0, pos.value.y.0).as_vec3() * 0.01
-            })
-            .unwrap_or_default()
+    /// the absolute location in double precision so large world coordinates survive
+    pub fn location(&self, map: &Asset) -> bevy::math::DVec3 {
+        location(map, self.transform)
     }
 
-    pub fn add_location(&self, map: &mut Asset, offset: bevy::math::Vec3) {
+    pub fn add_location(&self, map: &mut Asset, offset: bevy::math::DVec3) {
         let mut names = map.get_name_map();
         let Some(norm) = map.asset_data.exports[self.transform].get_normal_export_mut() else {
             return;
@@ -40,9 +26,9 @@ impl Actor {
             Some(scale) => {
                 if let Property::StructProperty(struc) = scale {
                     if let Property::VectorProperty(vec) = &mut struc.value[0] {
-                        vec.value.x.0 += offset.x as f64 * 100.0;
-                        vec.value.y.0 += offset.z as f64 * 100.0;
-                        vec.value.z.0 += offset.y as f64 * 100.0;
+                        vec.value.x.0 += offset.x * 100.0;
+                        vec.value.y.0 += offset.z * 100.0;
+                        vec.value.z.0 += offset.y * 100.0;
                     }
                 }
             }
@@ -68,9 +54,9 @@ impl Actor {
                             property_guid: None,
                             duplication_index: 0,
                             value: Vector::new(
-                                (-offset.x as f64).into(),
-                                (offset.z as f64).into(),
-                                (offset.y as f64).into(),
+                                (offset.x * 100.0).into(),
+                                (offset.z * 100.0).into(),
+                                (offset.y * 100.0).into(),
                             ),
                         })],
                     }));
@@ -79,32 +65,7 @@ impl Actor {
     }
 
     pub fn rotation(&self, map: &Asset) -> bevy::math::Quat {
-        map.asset_data.exports[self.transform]
-            .get_normal_export()
-            .map(|norm| {
-                norm.properties
-                    .iter()
-                    .rev()
-                    .find_map(|prop| {
-                        if let Property::StructProperty(struc) = prop {
-                            if struc.name == ROTATION {
-                                return cast!(Property, RotatorProperty, &struc.value[0]);
-                            }
-                        }
-                        None
-                    })
-                    .map(|rot| {
-                        bevy::math::DQuat::from_euler(
-                            bevy::math::EulerRot::XYZ,
-                            rot.value.x.0.to_radians(),
-                            -rot.value.y.0.to_radians(),
-                            rot.value.z.0.to_radians(),
-                        )
-                        .as_quat()
-                    })
-                    .unwrap_or_default()
-            })
-            .unwrap_or_default()
+        rotation(map, self.transform)
     }
 
     pub fn combine_rotation(&self, map: &mut Asset, offset: bevy::math::Quat) {
@@ -169,20 +130,7 @@ impl Actor {
     }
 
     pub fn scale(&self, map: &Asset) -> bevy::math::Vec3 {
-        map.asset_data.exports[self.transform]
-            .get_normal_export()
-            .and_then(|norm| {
-                norm.properties.iter().rev().find_map(|prop| {
-                    if let Property::StructProperty(struc) = prop {
-                        if struc.name == SCALE {
-                            return cast!(Property, VectorProperty, &struc.value[0]);
-                        }
-                    }
-                    None
-                })
-            })
-            .map(|rot| bevy::math::dvec3(rot.value.x.0, rot.value.z.0, rot.value.y.0).as_vec3())
-            .unwrap_or(bevy::math::Vec3::ONE)
+        scale(map, self.transform)
     }
 
     pub fn mul_scale(&self, map: &mut Asset, offset: bevy::math::Vec3) {
@@ -236,11 +184,78 @@ impl Actor {
         }
     }
 
-    pub fn transform(&self, map: &Asset) -> bevy::prelude::Transform {
+    /// the render transform relative to the floating origin
+    pub fn transform(&self, map: &Asset, origin: bevy::math::DVec3) -> bevy::prelude::Transform {
         bevy::prelude::Transform {
-            translation: self.location(map),
+            translation: (self.location(map) - origin).as_vec3(),
             rotation: self.rotation(map),
             scale: self.scale(map),
         }
     }
 }
+
+/// the location of any component export in double precision
+pub fn location(map: &Asset, export: usize) -> bevy::math::DVec3 {
+    map.asset_data.exports[export]
+        .get_normal_export()
+        .and_then(|norm| {
+            norm.properties.iter().rev().find_map(|prop| {
+                if let Property::StructProperty(struc) = prop {
+                    if struc.name == LOCATION {
+                        return cast!(Property, VectorProperty, &struc.value[0]);
+                    }
+                }
+                None
+            })
+        })
+        .map(|pos| bevy::math::dvec3(pos.value.x.0, pos.value.z.0, pos.value.y.0) * 0.01)
+        .unwrap_or_default()
+}
+
+/// the rotation of any component export
+pub fn rotation(map: &Asset, export: usize) -> bevy::math::Quat {
+    map.asset_data.exports[export]
+        .get_normal_export()
+        .map(|norm| {
+            norm.properties
+                .iter()
+                .rev()
+                .find_map(|prop| {
+                    if let Property::StructProperty(struc) = prop {
+                        if struc.name == ROTATION {
+                            return cast!(Property, RotatorProperty, &struc.value[0]);
+                        }
+                    }
+                    None
+                })
+                .map(|rot| {
+                    bevy::math::DQuat::from_euler(
+                        bevy::math::EulerRot::XYZ,
+                        rot.value.x.0.to_radians(),
+                        -rot.value.y.0.to_radians(),
+                        rot.value.z.0.to_radians(),
+                    )
+                    .as_quat()
+                })
+                .unwrap_or_default()
+        })
+        .unwrap_or_default()
+}
+
+/// the scale of any component export
+pub fn scale(map: &Asset, export: usize) -> bevy::math::Vec3 {
+    map.asset_data.exports[export]
+        .get_normal_export()
+        .and_then(|norm| {
+            norm.properties.iter().rev().find_map(|prop| {
+                if let Property::StructProperty(struc) = prop {
+                    if struc.name == SCALE {
+                        return cast!(Property, VectorProperty, &struc.value[0]);
+                    }
+                }
+                None
+            })
+        })
+        .map(|rot| bevy::math::dvec3(rot.value.x.0, rot.value.z.0, rot.value.y.0).as_vec3())
+        .unwrap_or(bevy::math::Vec3::ONE)
+}
@@ -3,7 +3,31 @@ use unreal_asset::properties::{
     array_property::ArrayProperty, int_property::BytePropertyValue,
     object_property::SoftObjectPath, soft_path_property::SoftObjectPathPropertyValue,
 };
+use unreal_asset::properties::{
+    enum_property::EnumProperty,
+    int_property::{
+        BoolProperty, ByteProperty, DoubleProperty, FloatProperty, Int16Property, Int64Property,
+        Int8Property, IntProperty, UInt16Property, UInt32Property, UInt64Property,
+    },
+    map_property::MapProperty,
+    object_property::ObjectProperty,
+    str_property::{NameProperty, StrProperty},
+    struct_property::StructProperty,
+};
+use unreal_asset::properties::{
+    material_input_property::MaterialExpression,
+    rich_curve_key_property::{
+        RichCurveInterpMode, RichCurveTangentMode, RichCurveTangentWeightMode,
+    },
+    view_target_blend_property::ViewTargetBlendFunction,
+};
 use unreal_asset::types::fname::ToSerializedName;
+use unreal_asset::types::vector::Color;
+use unreal_asset::{
+    containers::{IndexedMap, NameMap, SharedResource},
+    unversioned::{properties::UsmapPropertyData, Usmap},
+    Import,
+};
 
 impl Actor {
     pub fn show(
@@ -11,51 +35,510 @@ impl Actor {
         asset: &mut Asset,
         ui: &mut egui::Ui,
         transform: &mut bevy::prelude::Transform,
+        origin: bevy::math::DVec3,
         exports: &[String],
         imports: &[String],
     ) {
         ui.heading(&self.display);
-        fn export(
-            ui: &mut egui::Ui,
-            export: &mut crate::Export,
-            transform: &mut bevy::prelude::Transform,
-            exports: &[String],
-            imports: &[String],
-        ) {
-            if let Some(norm) = export.get_normal_export_mut() {
-                for prop in norm.properties.iter_mut() {
-                    property(ui, prop, transform, exports, imports);
-                }
-            }
-        }
-        export(
-            ui,
-            &mut asset.asset_data.exports[self.export],
-            transform,
+        // transform properties can be edited anywhere in the tree so compare before and after
+        let before = self.transform(asset, origin);
+        let cx = Context {
             exports,
             imports,
-        );
+            names: asset.get_name_map(),
+            mappings: asset.asset_data.mappings.as_ref(),
+            classes: classes(asset),
+            import_objects: &asset.imports,
+            expected: expected(ui, asset, exports),
+            owner: Default::default(),
+        };
+        export(ui, &mut asset.asset_data.exports[self.export], &cx);
         for i in asset.asset_data.exports[self.export]
             .get_base_export()
             .create_before_serialization_dependencies
             .clone()
             .iter()
+            .filter(|i| i.is_export())
         {
-            if let Some(ex) = asset.get_export_mut(*i) {
+            if let Some(ex) = asset.asset_data.exports.get_mut(i.index as usize - 1) {
                 let base = ex.get_base_export();
                 let name = base
                     .object_name
                     .get_content(|name| format!("{} ({})", name, i.index));
                 let index = -base.class_index.index - 1;
                 ui.push_id(base.serial_offset, |ui| {
-                    ui.collapsing(egui::RichText::new(name).strong(), |ui| {
-                        export(ui, ex, transform, exports, imports)
-                    })
-                    .header_response
-                    .on_hover_text(&imports[index as usize])
+                    ui.collapsing(egui::RichText::new(name).strong(), |ui| export(ui, ex, &cx))
+                        .header_response
+                        .on_hover_text(&imports[index as usize])
+                });
+            }
+        }
+        let after = self.transform(asset, origin);
+        if after != before {
+            *transform = after;
+        }
+    }
+
+    /// lists everything outside the actor which points at it
+    pub fn show_references(&self, asset: &Asset, ui: &mut egui::Ui, exports: &[String]) {
+        ui.collapsing("referenced by", |ui| {
+            // walking every export is slow so only redo it for another actor or map or once exports are added
+            let id = egui::Id::new("references");
+            let key = (self.export, exports.as_ptr() as usize, exports.len());
+            let refs = match ui
+                .data(|data| data.get_temp::<((usize, usize, usize), References)>(id))
+                .filter(|(cached, _)| *cached == key)
+            {
+                Some((_, refs)) => refs,
+                None => {
+                    let refs = std::sync::Arc::new(self.referenced_by(asset));
+                    ui.data_mut(|data| data.insert_temp(id, (key, refs.clone())));
+                    refs
+                }
+            };
+            if refs.is_empty() {
+                ui.label("nothing");
+            }
+            egui::Grid::new("references").striped(true).show(ui, |ui| {
+                for (export, path) in refs.iter() {
+                    let export = *export;
+                    if ui.link(&exports[export]).clicked() {
+                        ui.data_mut(|data| {
+                            data.insert_temp(egui::Id::new("reveal"), (export, false))
+                        });
+                    }
+                    ui.label(path);
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    /// shows a single component with its relative transform
+    pub fn show_component(
+        &self,
+        asset: &mut Asset,
+        ui: &mut egui::Ui,
+        transform: &mut bevy::prelude::Transform,
+        origin: bevy::math::DVec3,
+        component: usize,
+        exports: &[String],
+        imports: &[String],
+    ) {
+        ui.heading(&exports[component]);
+        ui.label(format!("component of {}", self.display));
+        let location = super::transform::location(asset, component) * 100.0;
+        let (x, y, z) = super::transform::rotation(asset, component)
+            .as_dquat()
+            .to_euler(bevy::math::EulerRot::XYZ);
+        let scale = super::transform::scale(asset, component);
+        egui::Grid::new("relative").striped(true).show(ui, |ui| {
+            // swizzle back to unreal's z-up coordinates
+            ui.label("relative location");
+            ui.label(format!(
+                "{:.2} {:.2} {:.2}",
+                location.x, location.z, location.y
+            ));
+            ui.end_row();
+            ui.label("relative rotation");
+            ui.label(format!(
+                "{:.2}° {:.2}° {:.2}°",
+                x.to_degrees(),
+                -y.to_degrees(),
+                z.to_degrees()
+            ));
+            ui.end_row();
+            ui.label("relative scale");
+            ui.label(format!("{:.2} {:.2} {:.2}", scale.x, scale.z, scale.y));
+            ui.end_row();
+        });
+        let before = self.transform(asset, origin);
+        let cx = Context {
+            exports,
+            imports,
+            names: asset.get_name_map(),
+            mappings: asset.asset_data.mappings.as_ref(),
+            classes: classes(asset),
+            import_objects: &asset.imports,
+            expected: expected(ui, asset, exports),
+            owner: Default::default(),
+        };
+        export(ui, &mut asset.asset_data.exports[component], &cx);
+        let after = self.transform(asset, origin);
+        if after != before {
+            *transform = after;
+        }
+    }
+}
+
+/// shows the properties every actor has in common and writes edits to all of them
+pub fn show_many(
+    actors: &[&Actor],
+    asset: &mut Asset,
+    ui: &mut egui::Ui,
+    exports: &[String],
+    imports: &[String],
+) {
+    ui.heading(format!("{} actors", actors.len()));
+    // match components by name since their indexes differ between actors
+    let mut groups = vec![(
+        None,
+        actors.iter().map(|actor| Some(actor.export)).collect(),
+    )];
+    for component in actors[0].components(asset) {
+        let Some(first) = asset.get_export(component) else {
+            continue;
+        };
+        let name = first.get_base_export().object_name.get_owned_content();
+        groups.push((
+            Some(name.clone()),
+            actors
+                .iter()
+                .map(|actor| {
+                    actor
+                        .components(asset)
+                        .into_iter()
+                        .find(|i| {
+                            asset
+                                .get_export(*i)
+                                .is_some_and(|ex| ex.get_base_export().object_name == name.as_str())
+                        })
+                        .map(|i| i.index as usize - 1)
+                })
+                .collect::<Vec<_>>(),
+        ));
+    }
+    for (name, indexes) in groups {
+        let Some(indexes) = indexes.into_iter().collect::<Option<Vec<_>>>() else {
+            continue;
+        };
+        let lists: Vec<_> = indexes
+            .iter()
+            .filter_map(|i| asset.asset_data.exports[*i].get_normal_export())
+            .map(|norm| norm.properties.as_slice())
+            .collect();
+        if lists.len() != indexes.len() {
+            continue;
+        }
+        let cx = Context {
+            exports,
+            imports,
+            names: asset.get_name_map(),
+            mappings: asset.asset_data.mappings.as_ref(),
+            classes: classes(asset),
+            import_objects: &asset.imports,
+            expected: expected(ui, asset, exports),
+            owner: Default::default(),
+        };
+        cx.owner.set(
+            asset.asset_data.exports[indexes[0]]
+                .get_base_export()
+                .class_index
+                .index,
+        );
+        let mut edits = Vec::new();
+        let mut show = |ui: &mut egui::Ui| {
+            for (name, values) in common(&lists) {
+                many(ui, vec![name], values, &cx, &mut edits);
+            }
+        };
+        match name {
+            Some(name) => {
+                ui.push_id(&name, |ui| {
+                    ui.collapsing(egui::RichText::new(&name).strong(), show)
+                });
+            }
+            None => show(ui),
+        }
+        for (path, edited) in edits {
+            for i in indexes.iter() {
+                let Some(norm) = asset.asset_data.exports[*i].get_normal_export_mut() else {
+                    continue;
+                };
+                if let Some(prop) = find(&mut norm.properties, &path) {
+                    *prop = edited.clone();
+                }
+            }
+        }
+    }
+}
+
+/// gets the properties with the same name and type in every list
+fn common(lists: &[&[Property]]) -> Vec<(String, Vec<Property>)> {
+    lists[0]
+        .iter()
+        .filter_map(|prop| {
+            let name = prop.get_name();
+            lists
+                .iter()
+                .map(|list| {
+                    list.iter()
+                        .find(|other| {
+                            other.get_name() == name
+                                && std::mem::discriminant(*other) == std::mem::discriminant(prop)
+                        })
+                        .cloned()
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(|values| (name.get_owned_content(), values))
+        })
+        .collect()
+}
+
+/// follows a path of property names through structs
+fn find<'a>(props: &'a mut [Property], path: &[String]) -> Option<&'a mut Property> {
+    let (first, rest) = path.split_first()?;
+    let prop = props
+        .iter_mut()
+        .find(|prop| prop.get_name().get_content(|name| name == first))?;
+    match rest.is_empty() {
+        true => Some(prop),
+        false => match prop {
+            Property::StructProperty(struc) => find(&mut struc.value, rest),
+            _ => None,
+        },
+    }
+}
+
+fn many(
+    ui: &mut egui::Ui,
+    path: Vec<String>,
+    values: Vec<Property>,
+    cx: &Context,
+    edits: &mut Vec<(Vec<String>, Property)>,
+) {
+    let mixed = values.iter().any(|val| val != &values[0]);
+    let name = path.last().cloned().unwrap_or_default();
+    // recurse into structs so editing one field doesn't overwrite the others
+    if values
+        .iter()
+        .all(|val| matches!(val, Property::StructProperty(_)))
+    {
+        let lists: Vec<_> = values
+            .iter()
+            .filter_map(|val| cast!(Property, StructProperty, val))
+            .map(|struc| struc.value.as_slice())
+            .collect();
+        ui.push_id(&name, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(&name).strong());
+                if mixed {
+                    ui.colored_label(ui.visuals().warn_fg_color, "mixed");
+                }
+                ui.collapsing("", |ui| {
+                    for (i, (name, values)) in common(&lists).into_iter().enumerate() {
+                        let mut path = path.clone();
+                        path.push(name);
+                        ui.push_id(i, |ui| many(ui, path, values, cx, edits));
+                    }
                 });
+            });
+        });
+        return;
+    }
+    let mut edited = values[0].clone();
+    // editing one field of anything else would copy the first actor's other fields to the rest
+    let container = !matches!(
+        values[0],
+        Property::BoolProperty(_)
+            | Property::ByteProperty(_)
+            | Property::EnumProperty(_)
+            | Property::FloatProperty(_)
+            | Property::DoubleProperty(_)
+            | Property::Int8Property(_)
+            | Property::Int16Property(_)
+            | Property::IntProperty(_)
+            | Property::Int64Property(_)
+            | Property::UInt16Property(_)
+            | Property::UInt32Property(_)
+            | Property::UInt64Property(_)
+            | Property::NameProperty(_)
+            | Property::StrProperty(_)
+            | Property::TextProperty(_)
+            | Property::ObjectProperty(_)
+            | Property::SoftObjectProperty(_)
+    );
+    ui.horizontal(|ui| {
+        if mixed {
+            ui.colored_label(ui.visuals().warn_fg_color, "≠")
+                .on_hover_text("this differs between the selected actors");
+        }
+        if mixed && container {
+            ui.label(egui::RichText::new(&name).strong());
+            ui.colored_label(ui.visuals().warn_fg_color, "mixed")
+                .on_hover_text("select a single actor to edit this");
+            return;
+        }
+        property(ui, &mut edited, cx);
+    });
+    if edited != values[0] {
+        edits.push((path, edited));
+    }
+}
+
+/// shows any export's properties followed by its extra data
+pub fn show_export(
+    asset: &mut Asset,
+    ui: &mut egui::Ui,
+    index: usize,
+    exports: &[String],
+    imports: &[String],
+) {
+    let cx = Context {
+        exports,
+        imports,
+        names: asset.get_name_map(),
+        mappings: asset.asset_data.mappings.as_ref(),
+        classes: classes(asset),
+        import_objects: &asset.imports,
+        expected: expected(ui, asset, exports),
+        owner: Default::default(),
+    };
+    let ex = &mut asset.asset_data.exports[index];
+    export(ui, ex, &cx);
+    if let Some(norm) = ex.get_normal_export_mut() {
+        ui.collapsing("extras", |ui| hex(ui, &mut norm.extras));
+    }
+}
+
+/// everything the property editors need from outside the property itself
+struct Context<'a> {
+    exports: &'a [String],
+    imports: &'a [String],
+    names: SharedResource<NameMap>,
+    mappings: Option<&'a Usmap>,
+    /// the class index of every export
+    classes: Vec<PackageIndex>,
+    import_objects: &'a [Import],
+    expected: Expected,
+    /// the class index of the export being shown
+    owner: std::cell::Cell<i32>,
+}
+
+fn classes(asset: &Asset) -> Vec<PackageIndex> {
+    asset
+        .asset_data
+        .exports
+        .iter()
+        .map(|ex| ex.get_base_export().class_index)
+        .collect()
+}
+
+/// the exports referencing an actor with the path to each reference
+type References = std::sync::Arc<Vec<(usize, String)>>;
+
+/// the class each object property points at on most exports of a class keyed by their class index
+type Expected = std::sync::Arc<std::collections::HashMap<(i32, String), String>>;
+
+fn expected(ui: &egui::Ui, asset: &Asset, exports: &[String]) -> Expected {
+    // tallying every export is slow so only redo it for another map or once exports are added
+    let id = egui::Id::new("expected classes");
+    let key = (exports.as_ptr() as usize, exports.len());
+    if let Some((_, expected)) = ui
+        .data(|data| data.get_temp::<((usize, usize), Expected)>(id))
+        .filter(|(cached, _)| *cached == key)
+    {
+        return expected;
+    }
+    let classes = classes(asset);
+    let mut counts =
+        std::collections::HashMap::<(i32, String), std::collections::HashMap<String, usize>>::new();
+    for ex in asset.asset_data.exports.iter() {
+        let owner = ex.get_base_export().class_index.index;
+        let Some(norm) = ex.get_normal_export() else {
+            continue;
+        };
+        let mut tally = |prop: &Property| {
+            let Some(obj) = cast!(Property, ObjectProperty, prop) else {
+                return;
+            };
+            if let Some(class) = class_of(&classes, &asset.imports, obj.value.index) {
+                *counts
+                    .entry((owner, prop.get_name().get_owned_content()))
+                    .or_default()
+                    .entry(class)
+                    .or_default() += 1;
+            }
+        };
+        for prop in norm.properties.iter() {
+            match prop {
+                Property::ArrayProperty(arr) => arr.value.iter().for_each(&mut tally),
+                prop => tally(prop),
+            }
+        }
+    }
+    let expected = std::sync::Arc::new(
+        counts
+            .into_iter()
+            .filter_map(|(key, classes)| {
+                let mut classes: Vec<_> = classes.into_iter().collect();
+                classes.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+                // a tie doesn't say which one's right
+                match classes.as_slice() {
+                    [(class, _)] => Some((key, class.clone())),
+                    [(class, most), (_, next), ..] if most > next => Some((key, class.clone())),
+                    _ => None,
+                }
+            })
+            .collect(),
+    );
+    ui.data_mut(|data| data.insert_temp(id, (key, expected.clone())));
+    expected
+}
+
+/// the class name of an export or import by its package index
+fn class_of(classes: &[PackageIndex], imports: &[Import], index: i32) -> Option<String> {
+    match index {
+        1..=i32::MAX => {
+            let class = classes.get(index as usize - 1)?;
+            match class.is_import() {
+                true => imports
+                    .get(class.index.unsigned_abs() as usize - 1)
+                    .map(|import| import.object_name.get_owned_content()),
+                false => None,
             }
         }
+        0 => None,
+        i32::MIN..0 => imports
+            .get(index.unsigned_abs() as usize - 1)
+            .map(|import| import.class_name.get_owned_content()),
+    }
+}
+
+/// the name of an export or import by its package index
+pub fn index_name<'a>(exports: &'a [String], imports: &'a [String], index: i32) -> Option<&'a str> {
+    match index {
+        1..=i32::MAX => exports.get(index as usize - 1).map(String::as_str),
+        0 => Some("null"),
+        i32::MIN..0 => imports
+            .get(index.unsigned_abs() as usize - 1)
+            .map(String::as_str),
+    }
+}
+
+impl Context<'_> {
+    fn name(&self, index: i32) -> Option<&str> {
+        index_name(self.exports, self.imports, index)
+    }
+
+    fn class(&self, index: i32) -> Option<String> {
+        class_of(&self.classes, self.import_objects, index)
+    }
+
+    /// what the property usually points at on other exports of the class being shown
+    fn expected(&self, name: &FName) -> Option<&str> {
+        self.expected
+            .get(&(self.owner.get(), name.get_owned_content()))
+            .map(String::as_str)
+    }
+}
+
+fn export(ui: &mut egui::Ui, export: &mut crate::Export, cx: &Context) {
+    cx.owner.set(export.get_base_export().class_index.index);
+    if let Some(norm) = export.get_normal_export_mut() {
+        for prop in norm.properties.iter_mut() {
+            property(ui, prop, cx);
+        }
     }
 }
 
@@ -84,19 +567,13 @@ fn option<T>(
     });
 }
 
-fn array_property(
-    ui: &mut egui::Ui,
-    arr: &mut ArrayProperty,
-    transform: &mut bevy::prelude::Transform,
-    exports: &[String],
-    imports: &[String],
-) {
+fn array_property(ui: &mut egui::Ui, arr: &mut ArrayProperty, cx: &Context) {
     ui.collapsing("", |ui| {
         let mut remove = None;
         for (i, entry) in arr.value.iter_mut().enumerate() {
             ui.push_id(i, |ui| {
                 ui.horizontal(|ui| {
-                    property(ui, entry, transform, exports, imports);
+                    property(ui, entry, cx);
                     if ui.button("x").clicked() {
                         remove = Some(i);
                     }
@@ -119,6 +596,393 @@ fn array_property(
     });
 }
 
+fn map_property(ui: &mut egui::Ui, map: &mut MapProperty, cx: &Context) {
+    ui.collapsing("", |ui| {
+        let mut remove = None;
+        // keys are hashed so they're edited on copies and the map is rebuilt on change
+        let mut keys: Vec<_> = map.value.keys().cloned().collect();
+        let mut rekeyed = false;
+        for (i, (key, value)) in keys.iter_mut().zip(map.value.values_mut()).enumerate() {
+            ui.push_id(i, |ui| {
+                ui.horizontal(|ui| {
+                    let old = key.clone();
+                    property(ui, key, cx);
+                    rekeyed |= key != &old;
+                    property(ui, value, cx);
+                    if ui.button("x").clicked() {
+                        remove = Some(i);
+                    }
+                })
+            });
+        }
+        // reject edits which would collide with another key
+        if rekeyed
+            && keys
+                .iter()
+                .enumerate()
+                .all(|(i, key)| !keys[..i].contains(key))
+        {
+            let values: Vec<_> = map.value.values().cloned().collect();
+            let mut rebuilt = IndexedMap::new();
+            for (key, value) in keys.into_iter().zip(values) {
+                rebuilt.insert(key, value);
+            }
+            map.value = rebuilt;
+        }
+        if let Some(i) = remove {
+            map.value.remove_by_index(i);
+        }
+        let (key, value) = match map.value.keys().last().zip(map.value.values().last()) {
+            Some((key, value)) => (Some(key.clone()), Some(value.clone())),
+            None => {
+                // fall back to the declared types with struct types from the mappings
+                let (key_struct, value_struct) = cx
+                    .mappings
+                    .and_then(|mappings| mappings.get_property(&map.name, &map.ancestry))
+                    .and_then(|mapped| match &mapped.property_data {
+                        UsmapPropertyData::UsmapMapPropertyData(data) => {
+                            Some((struct_type(&data.inner_type), struct_type(&data.value_type)))
+                        }
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+                (
+                    default_property(&map.key_type, FName::new_dummy("Key".into(), 0), key_struct),
+                    default_property(
+                        &map.value_type,
+                        FName::new_dummy("Value".into(), 0),
+                        value_struct,
+                    ),
+                )
+            }
+        };
+        match key.zip(value) {
+            Some((mut key, value)) => {
+                // bump copied keys until they're unique
+                let mut unique = false;
+                for _ in 0..=map.value.len() {
+                    unique = !map.value.keys().any(|other| other == &key);
+                    if unique || !bump(&mut key) {
+                        break;
+                    }
+                }
+                if ui
+                    .add_enabled(unique, egui::Button::new("+"))
+                    .on_disabled_hover_text("edit the last key to make room for another")
+                    .clicked()
+                {
+                    map.value.insert(key, value);
+                }
+            }
+            None => {
+                ui.horizontal(|ui| {
+                    ui.add_enabled(false, egui::Button::new("+"));
+                    ui.label(format!(
+                        "can't create {} -> {} entries from nothing",
+                        map.key_type.get_owned_content(),
+                        map.value_type.get_owned_content()
+                    ));
+                });
+            }
+        }
+    });
+}
+
+fn struct_type(data: &UsmapPropertyData) -> Option<String> {
+    match data {
+        UsmapPropertyData::UsmapStructPropertyData(data) => Some(data.struct_type.clone()),
+        _ => None,
+    }
+}
+
+/// increments a key in place returning whether it could be
+fn bump(key: &mut Property) -> bool {
+    match key {
+        Property::IntProperty(int) => int.value = int.value.wrapping_add(1),
+        Property::Int8Property(int) => int.value = int.value.wrapping_add(1),
+        Property::Int16Property(int) => int.value = int.value.wrapping_add(1),
+        Property::Int64Property(int) => int.value = int.value.wrapping_add(1),
+        Property::UInt16Property(int) => int.value = int.value.wrapping_add(1),
+        Property::UInt32Property(int) => int.value = int.value.wrapping_add(1),
+        Property::UInt64Property(int) => int.value = int.value.wrapping_add(1),
+        Property::ByteProperty(byte) => match &mut byte.value {
+            BytePropertyValue::Byte(byte) => *byte = byte.wrapping_add(1),
+            BytePropertyValue::FName(_) => return false,
+        },
+        Property::FloatProperty(float) => float.value.0 += 1.0,
+        Property::DoubleProperty(double) => double.value.0 += 1.0,
+        Property::NameProperty(name) => match &mut name.value {
+            FName::Backed { number, .. } | FName::Dummy { number, .. } => *number += 1,
+        },
+        Property::StrProperty(str) => str.value.get_or_insert_with(String::new).push('_'),
+        _ => return false,
+    }
+    true
+}
+
+macro_rules! simple {
+    ($prop:ident, $name:expr, $value:expr) => {
+        Property::$prop($prop {
+            name: $name,
+            ancestry: unreal_asset::unversioned::ancestry::Ancestry {
+                ancestry: Vec::new(),
+            },
+            property_guid: None,
+            duplication_index: 0,
+            value: $value,
+        })
+    };
+}
+
+/// creates an empty property from its serialized type name
+fn default_property(kind: &FName, name: FName, struct_type: Option<String>) -> Option<Property> {
+    Some(match kind.get_owned_content().as_str() {
+        "BoolProperty" => simple!(BoolProperty, name, false),
+        "IntProperty" => simple!(IntProperty, name, 0),
+        "Int8Property" => simple!(Int8Property, name, 0),
+        "Int16Property" => simple!(Int16Property, name, 0),
+        "Int64Property" => simple!(Int64Property, name, 0),
+        "UInt16Property" => simple!(UInt16Property, name, 0),
+        "UInt32Property" => simple!(UInt32Property, name, 0),
+        "UInt64Property" => simple!(UInt64Property, name, 0),
+        "FloatProperty" => simple!(FloatProperty, name, 0.0.into()),
+        "DoubleProperty" => simple!(DoubleProperty, name, 0.0.into()),
+        "StrProperty" => simple!(StrProperty, name, Some(String::new())),
+        "NameProperty" => simple!(NameProperty, name, FName::default()),
+        "ObjectProperty" => simple!(ObjectProperty, name, PackageIndex::new(0)),
+        "ByteProperty" => Property::ByteProperty(ByteProperty {
+            name,
+            ancestry: unreal_asset::unversioned::ancestry::Ancestry {
+                ancestry: Vec::new(),
+            },
+            property_guid: None,
+            duplication_index: 0,
+            enum_type: None,
+            value: BytePropertyValue::Byte(0),
+        }),
+        "EnumProperty" => Property::EnumProperty(EnumProperty {
+            name,
+            ancestry: unreal_asset::unversioned::ancestry::Ancestry {
+                ancestry: Vec::new(),
+            },
+            property_guid: None,
+            duplication_index: 0,
+            enum_type: None,
+            inner_type: None,
+            value: Some(FName::default()),
+        }),
+        // structs with custom serialisation can't be left empty
+        "StructProperty" => match struct_type {
+            Some(struct_type) if !CUSTOM_STRUCTS.contains(&struct_type.as_str()) => {
+                Property::StructProperty(StructProperty {
+                    name,
+                    ancestry: unreal_asset::unversioned::ancestry::Ancestry {
+                        ancestry: Vec::new(),
+                    },
+                    struct_type: Some(FName::new_dummy(struct_type, 0)),
+                    struct_guid: Some([0; 16].into()),
+                    property_guid: None,
+                    duplication_index: 0,
+                    serialize_none: true,
+                    value: Vec::new(),
+                })
+            }
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+const CUSTOM_STRUCTS: &[&str] = &[
+    "Vector",
+    "Vector2D",
+    "Vector4",
+    "IntPoint",
+    "Rotator",
+    "Quat",
+    "Box",
+    "Guid",
+    "Color",
+    "LinearColor",
+    "DateTime",
+    "Timespan",
+    "SoftObjectPath",
+    "GameplayTagContainer",
+];
+
+/// a searchable picker over every export and import
+fn object(ui: &mut egui::Ui, index: &mut PackageIndex, name: &FName, cx: &Context) {
+    let id = ui.id();
+    let label = cx.name(index.index).unwrap_or("invalid");
+    egui::ComboBox::from_id_source(id.with("object"))
+        .selected_text(label)
+        .width(200.0)
+        .show_ui(ui, |ui| {
+            let mut search = ui
+                .data(|data| data.get_temp::<String>(id.with("search")))
+                .unwrap_or_default();
+            let mut class = ui
+                .data(|data| data.get_temp::<String>(id.with("class")))
+                .unwrap_or_default();
+            ui.add(egui::TextEdit::singleline(&mut search).hint_text("search"));
+            ui.add(egui::TextEdit::singleline(&mut class).hint_text("class"));
+            let (lower, class_lower) = (search.to_lowercase(), class.to_lowercase());
+            let candidates: Vec<i32> = std::iter::once(0)
+                .chain(1..=cx.exports.len() as i32)
+                .chain((1..=cx.imports.len() as i32).map(|i| -i))
+                .filter(|i| {
+                    cx.name(*i)
+                        .is_some_and(|name| name.to_lowercase().contains(&lower))
+                        && (class_lower.is_empty()
+                            || cx
+                                .class(*i)
+                                .is_some_and(|class| class.to_lowercase().contains(&class_lower)))
+                })
+                .collect();
+            egui::ScrollArea::vertical().max_height(300.0).show_rows(
+                ui,
+                ui.text_style_height(&egui::TextStyle::Body),
+                candidates.len(),
+                |ui, range| {
+                    for i in candidates[range].iter() {
+                        let text = format!("{} ({})", cx.name(*i).unwrap_or_default(), i);
+                        ui.selectable_value(&mut index.index, *i, text)
+                            .on_hover_text(cx.class(*i).unwrap_or_default());
+                    }
+                },
+            );
+            ui.data_mut(|data| {
+                data.insert_temp(id.with("search"), search);
+                data.insert_temp(id.with("class"), class);
+            });
+        })
+        .response
+        .on_hover_text(format!(
+            "{} ({})",
+            cx.class(index.index).unwrap_or_default(),
+            index.index
+        ));
+    match index.index {
+        i @ 1..=i32::MAX if cx.name(i).is_some() => {
+            // the sidebar picks these up since only it can reach the ecs
+            if ui.button("go to").clicked() {
+                ui.data_mut(|data| {
+                    data.insert_temp(egui::Id::new("reveal"), (i as usize - 1, true))
+                });
+            }
+            if ui.button("select").clicked() {
+                ui.data_mut(|data| {
+                    data.insert_temp(egui::Id::new("reveal"), (i as usize - 1, false))
+                });
+            }
+        }
+        i if cx.name(i).is_none() => {
+            ui.colored_label(ui.visuals().error_fg_color, "out of range");
+        }
+        i if cx.class(i).is_some_and(|class| class == "Package") => {
+            ui.colored_label(ui.visuals().warn_fg_color, "points at a package");
+        }
+        _ => (),
+    }
+    let class = cx.class(index.index);
+    if let Some(expected) = cx.expected(name).filter(|expected| {
+        class
+            .as_ref()
+            .is_some_and(|class| class != expected && class != "Package")
+    }) {
+        ui.colored_label(ui.visuals().warn_fg_color, "wrong kind")
+            .on_hover_text(format!("this usually points at a {expected}"));
+    }
+}
+
+fn color(ui: &mut egui::Ui, col: &mut Color<u8>) {
+    let mut buf = [col.r, col.g, col.b, col.a];
+    if ui.color_edit_button_srgba_unmultiplied(&mut buf).changed() {
+        col.r = buf[0];
+        col.g = buf[1];
+        col.b = buf[2];
+        col.a = buf[3];
+    }
+}
+
+fn expression(ui: &mut egui::Ui, expr: &mut MaterialExpression) {
+    ui.collapsing("expression", |ui| {
+        ui.horizontal(|ui| {
+            ui.label("name:");
+            fname(ui, &mut expr.name);
+        });
+        ui.horizontal(|ui| {
+            ui.label("outer index:");
+            drag(ui, &mut expr.outer_index);
+        });
+        ui.label("extras:");
+        hex(ui, &mut expr.extras);
+    });
+}
+
+fn choice<T: PartialEq + Copy>(ui: &mut egui::Ui, id: &str, val: &mut T, options: &[(T, &str)]) {
+    let selected = options
+        .iter()
+        .find(|(option, _)| option == val)
+        .map_or("unknown", |(_, label)| label);
+    egui::ComboBox::from_id_source(id)
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            for (option, label) in options {
+                ui.selectable_value(val, *option, *label);
+            }
+        });
+}
+
+/// edits raw bytes as whitespace separated hex
+fn hex(ui: &mut egui::Ui, bytes: &mut Vec<u8>) {
+    // laying out megabytes of text every frame would stall the editor
+    if bytes.len() > 0x10000 {
+        ui.label(format!("{} bytes is too large to edit", bytes.len()));
+        return;
+    }
+    let id = ui.id().with("hex");
+    // keep the text while typing so half-written bytes aren't lost
+    let mut buf = ui
+        .data(|data| data.get_temp::<String>(id))
+        .unwrap_or_else(|| {
+            bytes
+                .chunks(16)
+                .map(|row| {
+                    row.iter()
+                        .map(|byte| format!("{byte:02x}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+    let res = egui::TextEdit::multiline(&mut buf)
+        .font(egui::TextStyle::Monospace)
+        .desired_width(f32::INFINITY)
+        .show(ui)
+        .response;
+    let parsed: Option<Vec<u8>> = buf
+        .split_whitespace()
+        .map(|byte| u8::from_str_radix(byte, 16).ok())
+        .collect();
+    match &parsed {
+        Some(parsed) => {
+            if res.changed() {
+                *bytes = parsed.clone();
+            }
+            ui.label(format!("{} bytes", parsed.len()));
+        }
+        None => {
+            ui.colored_label(ui.visuals().error_fg_color, "invalid hex");
+        }
+    }
+    ui.data_mut(|data| match res.has_focus() {
+        true => data.insert_temp(id, buf),
+        false => data.remove::<String>(id),
+    });
+}
+
 // I don't want to install OrderedFloat
 macro_rules! vector {
     ($ui:ident, $($val:expr),+) => {{
@@ -206,13 +1070,7 @@ fn soft_property(ui: &mut egui::Ui, path: &mut SoftObjectPathPropertyValue) {
     }
 }
 
-fn property(
-    ui: &mut egui::Ui,
-    prop: &mut Property,
-    transform: &mut bevy::prelude::Transform,
-    exports: &[String],
-    imports: &[String],
-) {
+fn property(ui: &mut egui::Ui, prop: &mut Property, cx: &Context) {
     match prop.get_name().get_owned_content().as_str() {
         "UCSModifiedProperties" | "UCSSerializationIndex" | "BlueprintCreatedComponents" => (),
         name => {
@@ -247,19 +1105,7 @@ fn property(
                             option(ui, &mut txt.culture_invariant_string, text, String::new);
                             option(ui, &mut txt.value, text, String::new);
                         }
-                        Property::ObjectProperty(obj) => {
-                            // let res = drag(ui, &mut obj.value.index);
-                            ui.add(
-                                egui::widgets::DragValue::new(&mut obj.value.index)
-                                    .range(-(imports.len() as i32)..=exports.len() as i32)
-                                    .speed(1.0),
-                            );
-                            ui.label(match obj.value.index {
-                                1..=i32::MAX => &exports[obj.value.index as usize - 1],
-                                0 => "null",
-                                i32::MIN..0 => &imports[-obj.value.index as usize - 1],
-                            });
-                        }
+                        Property::ObjectProperty(obj) => object(ui, &mut obj.value, &obj.name, cx),
                         Property::AssetObjectProperty(obj) => {
                             option(ui, &mut obj.value, text, String::new)
                         }
@@ -268,38 +1114,13 @@ fn property(
                             vector!(ui, point.value.x, point.value.y)
                         }
                         Property::VectorProperty(vec) => {
-                            let mut drag = |num| {
+                            for num in [&mut vec.value.x.0, &mut vec.value.y.0, &mut vec.value.z.0]
+                            {
                                 ui.add(
                                     egui::widgets::DragValue::new(num)
                                         .range(f64::MIN..=f64::MAX)
                                         .speed(1.0),
-                                )
-                            };
-                            if (drag(&mut vec.value.x.0)
-                                | drag(&mut vec.value.y.0)
-                                | drag(&mut vec.value.z.0))
-                            .changed()
-                            {
-                                vec.name.get_content(|name| match name {
-                                    LOCATION => {
-                                        transform.translation = bevy::math::dvec3(
-                                            vec.value.x.0,
-                                            vec.value.z.0,
-                                            vec.value.y.0,
-                                        )
-                                        .as_vec3()
-                                            * 0.01
-                                    }
-                                    SCALE => {
-                                        transform.scale = bevy::math::dvec3(
-                                            vec.value.x.0,
-                                            vec.value.z.0,
-                                            vec.value.y.0,
-                                        )
-                                        .as_vec3()
-                                    }
-                                    _ => (),
-                                })
+                                );
                             }
                         }
                         Property::Vector4Property(vec) => vector!(
@@ -329,21 +1150,9 @@ fn property(
                         ),
 
                         Property::RotatorProperty(rot) => {
-                            let mut drag =
-                                |num| ui.add(egui::widgets::DragValue::new(num).suffix("°"));
-                            if (drag(&mut rot.value.x.0)
-                                | drag(&mut rot.value.y.0)
-                                | drag(&mut rot.value.z.0))
-                            .changed()
-                                && rot.name == ROTATION
+                            for num in [&mut rot.value.x.0, &mut rot.value.y.0, &mut rot.value.z.0]
                             {
-                                transform.rotation = bevy::math::DQuat::from_euler(
-                                    bevy::math::EulerRot::XYZ,
-                                    rot.value.x.0.to_radians(),
-                                    -rot.value.y.0.to_radians(),
-                                    rot.value.z.0.to_radians(),
-                                )
-                                .as_quat()
+                                ui.add(egui::widgets::DragValue::new(num).suffix("°"));
                             }
                         }
                         Property::LinearColorProperty(col) => {
@@ -356,15 +1165,7 @@ fn property(
                                 col.color.a.0 = buf[3];
                             }
                         }
-                        Property::ColorProperty(col) => {
-                            let mut buf = [col.color.r, col.color.g, col.color.b, col.color.a];
-                            if ui.color_edit_button_srgba_unmultiplied(&mut buf).changed() {
-                                col.color.r = buf[0];
-                                col.color.g = buf[1];
-                                col.color.b = buf[2];
-                                col.color.a = buf[3];
-                            }
-                        }
+                        Property::ColorProperty(col) => color(ui, &mut col.color),
                         Property::TimeSpanProperty(time) => drag(ui, &mut time.ticks),
                         Property::DateTimeProperty(date) => drag(ui, &mut date.ticks),
                         Property::GuidProperty(guid) => {
@@ -372,34 +1173,9 @@ fn property(
                                 drag(ui, val)
                             }
                         }
-                        Property::SetProperty(set) => {
-                            array_property(ui, &mut set.value, transform, exports, imports)
-                        }
-                        Property::ArrayProperty(arr) => {
-                            array_property(ui, arr, transform, exports, imports)
-                        }
-                        Property::MapProperty(map) => {
-                            ui.collapsing("", |ui| {
-                                let mut remove = None;
-                                for (i, value) in map.value.values_mut().enumerate() {
-                                    ui.push_id(i, |ui| {
-                                        ui.horizontal(|ui| {
-                                            property(ui, value, transform, exports, imports);
-                                            if ui.button("x").clicked() {
-                                                remove = Some(i);
-                                            }
-                                        })
-                                    });
-                                }
-                                if let Some(i) = remove {
-                                    map.value.remove_by_index(i);
-                                }
-                                ui.horizontal(|ui| {
-                                    ui.add_enabled(false, egui::Button::new("+"));
-                                    ui.label("there is not enough data to add to maps");
-                                });
-                            });
-                        }
+                        Property::SetProperty(set) => array_property(ui, &mut set.value, cx),
+                        Property::ArrayProperty(arr) => array_property(ui, arr, cx),
+                        Property::MapProperty(map) => map_property(ui, map, cx),
                         Property::PerPlatformBoolProperty(bools) => {
                             ui.collapsing("", |ui| {
                                 for bool in bools.value.iter_mut() {
@@ -421,13 +1197,37 @@ fn property(
                                 }
                             });
                         }
-                        // Property::MaterialAttributesInputProperty(_) => todo!(),
-                        // Property::ExpressionInputProperty(_) => todo!(),
-                        // Property::ColorMaterialInputProperty(_) => todo!(),
-                        // Property::ScalarMaterialInputProperty(_) => todo!(),
-                        // Property::ShadingModelMaterialInputProperty(_) => todo!(),
-                        // Property::VectorMaterialInputProperty(_) => todo!(),
-                        // Property::Vector2MaterialInputProperty(_) => todo!(),
+                        Property::MaterialAttributesInputProperty(input) => {
+                            expression(ui, &mut input.value)
+                        }
+                        Property::ExpressionInputProperty(input) => {
+                            expression(ui, &mut input.value)
+                        }
+                        Property::ColorMaterialInputProperty(input) => {
+                            color(ui, &mut input.value.color);
+                            expression(ui, &mut input.material_expression);
+                        }
+                        Property::ScalarMaterialInputProperty(input) => {
+                            drag(ui, &mut input.value.0);
+                            expression(ui, &mut input.material_expression);
+                        }
+                        Property::ShadingModelMaterialInputProperty(input) => {
+                            drag(ui, &mut input.value);
+                            expression(ui, &mut input.material_expression);
+                        }
+                        Property::VectorMaterialInputProperty(input) => {
+                            vector!(
+                                ui,
+                                input.value.value.x.0,
+                                input.value.value.y.0,
+                                input.value.value.z.0
+                            );
+                            expression(ui, &mut input.material_expression);
+                        }
+                        Property::Vector2MaterialInputProperty(input) => {
+                            vector!(ui, input.value.value.x.0, input.value.value.y.0);
+                            expression(ui, &mut input.material_expression);
+                        }
                         Property::WeightedRandomSamplerProperty(rand) => sampler!(ui, rand),
                         Property::SkeletalMeshSamplingLODBuiltDataProperty(lod) => {
                             sampler!(ui, lod.sampler_property)
@@ -448,24 +1248,153 @@ fn property(
                         Property::MulticastDelegateProperty(del) => delegate!(ui, del),
                         Property::MulticastSparseDelegateProperty(del) => delegate!(ui, del),
                         Property::MulticastInlineDelegateProperty(del) => delegate!(ui, del),
-                        // Property::RichCurveKeyProperty(_) => todo!(),
-                        // Property::ViewTargetBlendParamsProperty(_) => todo!(),
-                        // Property::GameplayTagContainerProperty(_) => todo!(),
+                        Property::RichCurveKeyProperty(key) => {
+                            ui.collapsing("", |ui| {
+                                egui::Grid::new("key").show(ui, |ui| {
+                                    ui.label("interp mode");
+                                    choice(
+                                        ui,
+                                        "interp",
+                                        &mut key.interp_mode,
+                                        &[
+                                            (RichCurveInterpMode::Linear, "linear"),
+                                            (RichCurveInterpMode::Constant, "constant"),
+                                            (RichCurveInterpMode::Cubic, "cubic"),
+                                            (RichCurveInterpMode::None, "none"),
+                                        ],
+                                    );
+                                    ui.end_row();
+                                    ui.label("tangent mode");
+                                    choice(
+                                        ui,
+                                        "tangent",
+                                        &mut key.tangent_mode,
+                                        &[
+                                            (RichCurveTangentMode::Auto, "auto"),
+                                            (RichCurveTangentMode::User, "user"),
+                                            (RichCurveTangentMode::Break, "break"),
+                                            (RichCurveTangentMode::None, "none"),
+                                        ],
+                                    );
+                                    ui.end_row();
+                                    ui.label("tangent weight mode");
+                                    choice(
+                                        ui,
+                                        "weight",
+                                        &mut key.tangent_weight_mode,
+                                        &[
+                                            (RichCurveTangentWeightMode::WeightedNone, "none"),
+                                            (RichCurveTangentWeightMode::WeightedArrive, "arrive"),
+                                            (RichCurveTangentWeightMode::WeightedLeave, "leave"),
+                                            (RichCurveTangentWeightMode::WeightedBoth, "both"),
+                                        ],
+                                    );
+                                    ui.end_row();
+                                    for (label, val) in [
+                                        ("time", &mut key.time.0),
+                                        ("value", &mut key.value.0),
+                                        ("arrive tangent", &mut key.arrive_tangent.0),
+                                        ("arrive tangent weight", &mut key.arrive_tangent_weight.0),
+                                        ("leave tangent", &mut key.leave_tangent.0),
+                                        ("leave tangent weight", &mut key.leave_tangent_weight.0),
+                                    ] {
+                                        ui.label(label);
+                                        drag(ui, val);
+                                        ui.end_row();
+                                    }
+                                });
+                            });
+                        }
+                        Property::ViewTargetBlendParamsProperty(params) => {
+                            ui.collapsing("", |ui| {
+                                egui::Grid::new("params").show(ui, |ui| {
+                                    ui.label("blend time");
+                                    drag(ui, &mut params.blend_time.0);
+                                    ui.end_row();
+                                    ui.label("blend function");
+                                    choice(
+                                        ui,
+                                        "function",
+                                        &mut params.blend_function,
+                                        &[
+                                            (ViewTargetBlendFunction::VtBlendLinear, "linear"),
+                                            (ViewTargetBlendFunction::VtBlendCubic, "cubic"),
+                                            (ViewTargetBlendFunction::VtBlendEaseIn, "ease in"),
+                                            (ViewTargetBlendFunction::VtBlendEaseOut, "ease out"),
+                                            (
+                                                ViewTargetBlendFunction::VtBlendEaseInOut,
+                                                "ease in out",
+                                            ),
+                                            (ViewTargetBlendFunction::VtBlendMax, "max"),
+                                        ],
+                                    );
+                                    ui.end_row();
+                                    ui.label("blend exponent");
+                                    drag(ui, &mut params.blend_exp.0);
+                                    ui.end_row();
+                                    ui.label("lock outgoing");
+                                    ui.checkbox(&mut params.lock_outgoing, "");
+                                    ui.end_row();
+                                });
+                            });
+                        }
+                        Property::GameplayTagContainerProperty(tags) => {
+                            ui.collapsing("", |ui| {
+                                let mut remove = None;
+                                for (i, tag) in tags.value.iter_mut().enumerate() {
+                                    ui.push_id(i, |ui| {
+                                        ui.horizontal(|ui| {
+                                            fname(ui, tag);
+                                            if ui.button("x").clicked() {
+                                                remove = Some(i);
+                                            }
+                                        })
+                                    });
+                                }
+                                if let Some(i) = remove {
+                                    tags.value.remove(i);
+                                }
+                                // only named once there's a tag so empty names don't pile up in the map
+                                let id = ui.id().with("new tag");
+                                let mut new = ui
+                                    .data(|data| data.get_temp::<String>(id))
+                                    .unwrap_or_default();
+                                ui.horizontal(|ui| {
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut new).hint_text("new tag"),
+                                    );
+                                    if ui
+                                        .add_enabled(!new.trim().is_empty(), egui::Button::new("+"))
+                                        .clicked()
+                                    {
+                                        let tag = cx.names.get_mut().add_fname(new.trim());
+                                        tags.value.push(tag);
+                                        new.clear();
+                                    }
+                                });
+                                ui.data_mut(|data| data.insert_temp(id, new));
+                            });
+                        }
                         Property::SmartNameProperty(name) => fname(ui, &mut name.display_name),
                         Property::StructProperty(str) => {
                             ui.collapsing("", |ui| {
                                 for (i, val) in str.value.iter_mut().enumerate() {
-                                    ui.push_id(i, |ui| {
-                                        property(ui, val, transform, exports, imports)
-                                    });
+                                    ui.push_id(i, |ui| property(ui, val, cx));
                                 }
                             });
                         }
                         Property::EnumProperty(enm) => {
                             option(ui, &mut enm.value, fname, FName::default)
                         }
-                        // Property::UnknownProperty(unknown) => todo!(),
-                        _ => (),
+                        Property::UnknownProperty(unknown) => {
+                            if let Some(ty) = &unknown.serialized_type {
+                                ui.label(ty.get_owned_content());
+                            }
+                            ui.collapsing("", |ui| hex(ui, &mut unknown.value));
+                        }
+                        _ => {
+                            ui.label("unsupported");
+                        }
                     };
                 })
             });
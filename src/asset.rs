@@ -1,9 +1,38 @@
 use std::{fs::File, io::BufReader, path::Path};
 
-use unreal_asset::{engine_version::EngineVersion, error::Error, Asset};
+use unreal_asset::{engine_version::EngineVersion, error::Error, unversioned::Usmap, Asset};
+
+/// mappings shared between parses which lend out owned copies since unreal_asset takes them by value
+pub struct Mappings {
+    usmap: Usmap,
+    /// copies handed back after parsing so only parses running at once need their own
+    spare: std::sync::Mutex<Vec<Usmap>>,
+}
+
+impl Mappings {
+    fn lend(&self) -> Usmap {
+        self.spare
+            .lock()
+            .ok()
+            .and_then(|mut spare| spare.pop())
+            .unwrap_or_else(|| self.usmap.clone())
+    }
+
+    /// takes the mappings back from an asset which is done parsing
+    pub fn give_back(&self, asset: &mut super::Asset) {
+        if let (Some(usmap), Ok(mut spare)) = (asset.asset_data.mappings.take(), self.spare.lock())
+        {
+            spare.push(usmap);
+        }
+    }
+}
 
 /// creates an asset from the specified path and version
-pub fn open(file: impl AsRef<Path>, version: EngineVersion) -> Result<super::Asset, Error> {
+pub fn open(
+    file: impl AsRef<Path>,
+    version: EngineVersion,
+    usmap: Option<&Mappings>,
+) -> Result<super::Asset, Error> {
     Asset::new(
         super::Wrapper::File(BufReader::new(File::open(&file)?)),
         File::open(file.as_ref().with_extension("uexp"))
@@ -11,10 +40,68 @@ pub fn open(file: impl AsRef<Path>, version: EngineVersion) -> Result<super::Ass
             .map(BufReader::new)
             .map(super::Wrapper::File),
         version,
-        None,
+        usmap.map(Mappings::lend),
     )
 }
 
+/// reads the mappings unversioned packages need to parse properties
+pub fn mappings(usmap: impl AsRef<Path>) -> Result<Mappings, Error> {
+    Ok(Mappings {
+        usmap: Usmap::new(BufReader::new(File::open(usmap)?))?,
+        spare: Default::default(),
+    })
+}
+
+/// guesses the version a package was cooked with by which parses best
+pub fn detect(file: impl AsRef<Path>, usmap: Option<&Mappings>) -> Option<usize> {
+    let give_back = |asset: &mut super::Asset| {
+        if let Some(usmap) = usmap {
+            usmap.give_back(asset)
+        }
+    };
+    // versioned packages record the version in the summary
+    if let Some(i) = open(&file, EngineVersion::UNKNOWN, usmap)
+        .ok()
+        .and_then(|mut asset| {
+            give_back(&mut asset);
+            let version = asset.get_engine_version();
+            super::VERSIONS
+                .iter()
+                .position(|(candidate, _)| *candidate == version)
+        })
+    {
+        if i > 1 {
+            return Some(i);
+        }
+    }
+    // otherwise try newest first since that's the most likely to be modded
+    let mut best: Option<(usize, usize)> = None;
+    for i in (2..super::VERSIONS.len()).rev() {
+        let Ok(mut asset) = open(&file, super::VERSIONS[i].0, usmap) else {
+            continue;
+        };
+        give_back(&mut asset);
+        let raw = raw_exports(&asset);
+        if best.map_or(true, |(_, fewest)| raw < fewest) {
+            best = Some((i, raw));
+        }
+        if raw == 0 {
+            break;
+        }
+    }
+    best.map(|(i, _)| i)
+}
+
+/// how many exports failed to parse
+pub fn raw_exports<C: std::io::Read + std::io::Seek>(asset: &Asset<C>) -> usize {
+    asset
+        .asset_data
+        .exports
+        .iter()
+        .filter(|ex| matches!(ex, unreal_asset::Export::RawExport(_)))
+        .count()
+}
+
 /// saves an asset's data to the specified path
 pub fn save<C: std::io::Read + std::io::Seek>(
     asset: &mut Asset<C>,
@@ -32,28 +119,9 @@ pub fn save<C: std::io::Read + std::io::Seek>(
     )
 }
 
-#[test]
-fn path_with_mount() {
-    let game = "Lava";
-    let path = "/Theme04_Wasteland/Mesh/SM_TH04_Asphalt_Piece_D";
-    let mount = "../../../Lava/Plugins/Objects/Theme_Sets/Theme04_Wasteland/Content/Mesh/";
-    let file = "SM_TH04_Asphalt_Piece_D.uasset";
-    let path = match path.split('/').nth(1) {
-        Some("Game") => path.replace("/Game", &format!("{}/Content", game)),
-        Some("Engine") => path.replace("/Engine/", "Engine/Content/"),
-        Some(plugin) => path.replace(&format!("/{plugin}/"), &format!("{plugin}/Content/")),
-        _ => path.into(),
-    };
-    assert_eq!(
-        path,
-        "Theme04_Wasteland/Content/Mesh/SM_TH04_Asphalt_Piece_D"
-    );
-    assert!((mount.to_owned() + file).ends_with(&(path + ".uasset")));
-}
-
+/// reads a package from the highest priority layer it parses from
 pub fn get<T>(
-    paks: &super::Paks,
-    cache: Option<&std::path::Path>,
+    vfs: &super::vfs::Vfs,
     path: &str,
     version: unreal_asset::engine_version::EngineVersion,
     func: impl Fn(
@@ -61,43 +129,42 @@ pub fn get<T>(
         Option<super::Wrapper>,
     ) -> Result<T, unreal_asset::error::Error>,
 ) -> Option<T> {
-    let path = match path.split('/').nth(1) {
-        Some("Game") => path.replace("/Game", &format!("{}/Content", paks.0)),
-        Some("Engine") => path.replace("/Engine/", "Engine/Content/"),
-        Some(plugin) => path.replace(&format!("/{plugin}/"), &format!("{plugin}/Content/")),
-        _ => path.into(),
-    };
-    let loose = paks.1.join(&path);
-    let mesh = loose.with_extension("uasset");
-    if mesh.exists() {
-        if let Ok(asset) = open(mesh, version).and_then(|asset| {
-            func(
-                asset,
-                std::fs::File::open(loose.with_extension("ubulk"))
+    use super::vfs::Source;
+    vfs.lookup(path)
+        .into_iter()
+        .find_map(|(layer, path)| match &vfs.layers[layer].source {
+            Source::Loose(root) => {
+                let loose = root.join(&path);
+                open(loose.with_extension("uasset"), version, vfs.usmap.as_ref())
+                    .and_then(|mut asset| {
+                        // parsing is done so the mappings can go to the next package
+                        if let Some(usmap) = vfs.usmap.as_ref() {
+                            usmap.give_back(&mut asset);
+                        }
+                        func(
+                            asset,
+                            std::fs::File::open(loose.with_extension("ubulk"))
+                                .ok()
+                                .map_or_else(
+                                    || std::fs::File::open(loose.with_extension("uptnl")).ok(),
+                                    Some,
+                                )
+                                .map(std::io::BufReader::new)
+                                .map(super::Wrapper::File),
+                        )
+                    })
                     .ok()
-                    .map_or_else(
-                        || std::fs::File::open(loose.with_extension("uptnl")).ok(),
-                        Some,
-                    )
-                    .map(std::io::BufReader::new)
-                    .map(super::Wrapper::File),
-            )
-        }) {
-            return Some(asset);
-        }
-    }
-    for (pak_file, pak) in paks.2.iter() {
-        if let Ok(asset) = read(pak, pak_file, cache, &path, version, &func) {
-            return Some(asset);
-        }
-    }
-    None
+            }
+            Source::Pak(pak) => pak.reader().and_then(|reader| {
+                read(reader, &pak.path, vfs.usmap.as_ref(), &path, version, &func).ok()
+            }),
+        })
 }
 
 fn read<T>(
     pak: &repak::PakReader,
     pak_file: &std::path::Path,
-    cache: Option<&std::path::Path>,
+    usmap: Option<&Mappings>,
     path: &str,
     version: unreal_asset::engine_version::EngineVersion,
     func: impl Fn(
@@ -105,80 +172,59 @@ fn read<T>(
         Option<super::Wrapper>,
     ) -> Result<T, unreal_asset::error::Error>,
 ) -> Result<T, unreal_asset::error::Error> {
-    let mut path: String = path.into();
-    let mesh = path.clone() + ".uasset";
-    let Some(file) = pak
-        .files()
-        .into_iter()
-        .find(|file| (pak.mount_point().to_string() + file).ends_with(&mesh))
-    else {
-        return Err(Error::NoData("asset not found in pak".into()));
-    };
-    path = file.trim_end_matches(".uasset").into();
-    let make = |ext: &str| path.clone() + ext;
+    let make = |ext: &str| path.to_string() + ext;
     let (mesh, exp, bulk, uptnl) = (
         make(".uasset"),
         make(".uexp"),
         make(".ubulk"),
         make(".uptnl"),
     );
-    let cached = |path: &str| cache.unwrap().join(path.trim_start_matches('/'));
     let pak_file = &mut std::io::BufReader::new(std::fs::File::open(pak_file)?);
-    match cache {
-        Some(_)
-            if cached(&mesh).exists() ||
-            // try to create cache if it doesn't exist
-            (
-                std::fs::create_dir_all(cached(&path).parent().unwrap()).is_ok() &&
-                pak.read_file(&mesh, pak_file, &mut std::fs::File::create(cached(&mesh))?).is_ok() &&
-                // we don't care whether these are successful in case they don't exist
-                pak.read_file(&exp, pak_file, &mut std::fs::File::create(cached(&exp))?).map_or(true,|_| true) &&
-                pak.read_file(&bulk, pak_file, &mut std::fs::File::create(cached(&bulk))?).map_or(true,|_| true) &&
-                pak.read_file(&uptnl, pak_file, &mut std::fs::File::create(cached(&uptnl))?).map_or(true,|_| true)
-             ) =>
-        {
-            func(
-                unreal_asset::Asset::new(
-                    super::Wrapper::File(std::io::BufReader::new(std::fs::File::open(cached(
-                        &mesh,
-                    ))?)),
-                    std::fs::File::open(cached(&exp))
-                        .ok()
-                        .map(std::io::BufReader::new)
-                        .map(super::Wrapper::File),
-                    version,
-                    None,
-                )?,
-                std::fs::File::open(cached(&bulk))
-                    .ok()
-                    .map_or_else(|| std::fs::File::open(cached(&uptnl)).ok(), Some)
-                    .map(std::io::BufReader::new)
-                    .map(super::Wrapper::File),
-            )
-        }
-        // if the cache cannot be created fall back to storing in memory
-        _ => func(
-            unreal_asset::Asset::new(
-                super::Wrapper::Bytes(std::io::Cursor::new(pak.get(&mesh, pak_file).map_err(
-                    |e| {
-                        unreal_asset::error::Error::no_data(match e {
-                            repak::Error::Oodle => "oodle paks are unsupported atm".to_string(),
-                            e => format!("error reading pak: {e}"),
-                        })
-                    },
-                )?)),
-                pak.get(&exp, pak_file)
-                    .ok()
-                    .map(std::io::Cursor::new)
-                    .map(super::Wrapper::Bytes),
-                version,
-                None,
-            )?,
-            pak.get(&bulk, pak_file)
-                .ok()
-                .map_or_else(|| pak.get(&uptnl, pak_file).ok(), Some)
-                .map(std::io::Cursor::new)
-                .map(super::Wrapper::Bytes),
-        ),
+    let mut asset = unreal_asset::Asset::new(
+        super::Wrapper::Bytes(std::io::Cursor::new(pak.get(&mesh, pak_file).map_err(
+            |e| {
+                unreal_asset::error::Error::no_data(match e {
+                    repak::Error::Oodle => "oodle paks are unsupported atm".to_string(),
+                    e => format!("error reading pak: {e}"),
+                })
+            },
+        )?)),
+        pak.get(&exp, pak_file)
+            .ok()
+            .map(std::io::Cursor::new)
+            .map(super::Wrapper::Bytes),
+        version,
+        usmap.map(Mappings::lend),
+    )?;
+    if let Some(usmap) = usmap {
+        usmap.give_back(&mut asset);
     }
+    func(
+        asset,
+        pak.get(&bulk, pak_file)
+            .ok()
+            .map_or_else(|| pak.get(&uptnl, pak_file).ok(), Some)
+            .map(std::io::Cursor::new)
+            .map(super::Wrapper::Bytes),
+    )
+}
+
+/// the class of a package's main export e.g StaticMesh
+pub fn class(
+    vfs: &super::vfs::Vfs,
+    path: &str,
+    version: unreal_asset::engine_version::EngineVersion,
+) -> Option<String> {
+    use unreal_asset::exports::ExportBaseTrait;
+    get(vfs, path, version, |asset, _| {
+        Ok(asset
+            .asset_data
+            .exports
+            .iter()
+            .map(|ex| ex.get_base_export())
+            .find(|ex| ex.outer_index.index == 0)
+            .and_then(|ex| asset.get_import(ex.class_index))
+            .map(|import| import.object_name.get_owned_content()))
+    })
+    .flatten()
 }
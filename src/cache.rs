@@ -0,0 +1,271 @@
+use super::*;
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+use std::io::{self, Read, Write};
+
+/// positions, indices, the first uv channel and material paths of a mesh
+pub type MeshData = (Vec<Vec3>, Vec<u32>, Vec<Vec2>, Vec<String>);
+/// width, height and bgra data of a texture
+pub type TexData = (u32, u32, Vec<u8>);
+
+/// bumped whenever the layout of cached files changes so old ones are never read
+const FORMAT: u32 = 1;
+
+/// decoded meshes and textures of a profile kept between runs
+#[derive(Clone)]
+pub struct Cache {
+    dir: std::path::PathBuf,
+    /// what else decoding depends on besides the asset itself
+    key: String,
+}
+
+impl Cache {
+    pub fn new(profile: &Profile) -> Option<Self> {
+        // a different usmap can decode the same package differently
+        let usmap = profile.usmap.as_ref().map_or_else(String::new, |usmap| {
+            let (size, modified) = std::fs::metadata(usmap).as_ref().map_or((0, 0), vfs::stamp);
+            format!("{usmap}:{size}:{modified}")
+        });
+        Some(Self {
+            dir: dir(profile.id)?,
+            key: format!("{FORMAT}:{}:{usmap}", profile.version),
+        })
+    }
+
+    /// where an asset is cached so it misses once the pak or loose file, version, mappings or format change
+    fn file(&self, vfs: &vfs::Vfs, path: &str, ext: &str) -> Option<std::path::PathBuf> {
+        let hash = hash(format!("{}:{}", self.key, vfs.identity(path)?).as_bytes());
+        Some(self.dir.join(format!("{hash:016x}.{ext}")))
+    }
+
+    /// opens a cached file marking it as recently used
+    fn open(&self, file: std::path::PathBuf) -> Option<(io::BufReader<std::fs::File>, u64)> {
+        let file = std::fs::File::options()
+            .read(true)
+            .write(true)
+            .open(file)
+            .ok()?;
+        let _ = file.set_modified(std::time::SystemTime::now());
+        let size = file.metadata().ok()?.len();
+        Some((io::BufReader::new(file), size))
+    }
+
+    fn save(
+        &self,
+        file: Option<std::path::PathBuf>,
+        write: impl Fn(&mut dyn Write) -> io::Result<()>,
+    ) {
+        let Some(file) = file else { return };
+        let _ = std::fs::create_dir_all(&self.dir);
+        // written beside the entry and renamed over it so a crash never leaves half an entry to read
+        let temp = file.with_extension(format!("{:016x}.tmp", id()));
+        let Ok(mut out) = std::fs::File::create(&temp).map(io::BufWriter::new) else {
+            return;
+        };
+        let written = write(&mut out).and_then(|_| out.flush());
+        drop(out);
+        if written.and_then(|_| std::fs::rename(&temp, file)).is_err() {
+            let _ = std::fs::remove_file(temp);
+        }
+    }
+
+    pub fn mesh(&self, vfs: &vfs::Vfs, path: &str) -> Option<MeshData> {
+        let (mut file, size) = self.open(self.file(vfs, path, "mesh")?)?;
+        read_mesh(&mut file, size).ok()
+    }
+
+    pub fn save_mesh(&self, vfs: &vfs::Vfs, path: &str, mesh: &MeshData) {
+        self.save(self.file(vfs, path, "mesh"), |out| write_mesh(out, mesh))
+    }
+
+    pub fn texture(&self, vfs: &vfs::Vfs, path: &str) -> Option<TexData> {
+        let (mut file, size) = self.open(self.file(vfs, path, "tex")?)?;
+        read_texture(&mut file, size).ok()
+    }
+
+    pub fn save_texture(&self, vfs: &vfs::Vfs, path: &str, tex: &TexData) {
+        self.save(self.file(vfs, path, "tex"), |out| write_texture(out, tex))
+    }
+
+    /// removes the least recently used entries until the cache fits in the limit
+    pub fn evict(&self, limit: u64) {
+        let Ok(dir) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut files: Vec<_> = dir
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let meta = entry.metadata().ok()?;
+                Some((meta.modified().ok()?, meta.len(), entry.path()))
+            })
+            .collect();
+        let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+        files.sort_by_key(|(modified, ..)| *modified);
+        for (_, len, file) in files {
+            if total <= limit {
+                break;
+            }
+            if std::fs::remove_file(file).is_ok() {
+                total -= len;
+            }
+        }
+    }
+}
+
+fn dir(profile: u64) -> Option<std::path::PathBuf> {
+    config().map(|config| config.join("decoded").join(format!("{profile:016x}")))
+}
+
+/// a new profile id which won't match any made before it
+pub fn id() -> u64 {
+    static COUNT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |time| time.as_nanos() as u64);
+    time.wrapping_add(COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed))
+}
+
+/// fnv-1a since the std hashers can change between rust versions
+pub fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// removes the raw files older versions copied out of the paks since nothing reads them now
+pub fn remove_old() {
+    let Some(old) = config().map(|config| config.join("cache")) else {
+        return;
+    };
+    if old.exists() {
+        std::thread::spawn(move || std::fs::remove_dir_all(old));
+    }
+}
+
+/// how many bytes a profile's cache takes up
+pub fn usage(profile: u64) -> u64 {
+    dir(profile)
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .map(|dir| {
+            dir.filter_map(Result::ok)
+                .filter_map(|entry| entry.metadata().ok())
+                .map(|meta| meta.len())
+                .sum()
+        })
+        .unwrap_or_default()
+}
+
+pub fn clear(profile: u64) -> io::Result<()> {
+    match dir(profile) {
+        Some(dir) if dir.exists() => std::fs::remove_dir_all(dir),
+        _ => Ok(()),
+    }
+}
+
+/// reads a length prefix checking that many items of the given size could fit in the file
+fn count(file: &mut impl Read, size: u64, each: u64) -> io::Result<u32> {
+    let len = file.read_u32::<LE>()?;
+    match len as u64 * each <= size {
+        true => Ok(len),
+        false => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{len} items can't fit in {size} bytes"),
+        )),
+    }
+}
+
+fn read_mesh(file: &mut impl Read, size: u64) -> io::Result<MeshData> {
+    let len = count(file, size, 12)?;
+    let positions = (0..len)
+        .map(|_| -> io::Result<_> {
+            Ok(Vec3::new(
+                file.read_f32::<LE>()?,
+                file.read_f32::<LE>()?,
+                file.read_f32::<LE>()?,
+            ))
+        })
+        .collect::<io::Result<_>>()?;
+    let len = count(file, size, 4)?;
+    let indices = (0..len)
+        .map(|_| file.read_u32::<LE>())
+        .collect::<io::Result<_>>()?;
+    let len = count(file, size, 8)?;
+    let uvs = (0..len)
+        .map(|_| -> io::Result<_> { Ok(Vec2::new(file.read_f32::<LE>()?, file.read_f32::<LE>()?)) })
+        .collect::<io::Result<_>>()?;
+    let len = count(file, size, 4)?;
+    let mats = (0..len)
+        .map(|_| -> io::Result<_> {
+            let mut buf = vec![0; count(file, size, 1)? as usize];
+            file.read_exact(&mut buf)?;
+            String::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+        .collect::<io::Result<_>>()?;
+    Ok((positions, indices, uvs, mats))
+}
+
+fn write_mesh(file: &mut dyn Write, (positions, indices, uvs, mats): &MeshData) -> io::Result<()> {
+    file.write_u32::<LE>(positions.len() as u32)?;
+    for pos in positions {
+        file.write_f32::<LE>(pos.x)?;
+        file.write_f32::<LE>(pos.y)?;
+        file.write_f32::<LE>(pos.z)?;
+    }
+    file.write_u32::<LE>(indices.len() as u32)?;
+    for index in indices {
+        file.write_u32::<LE>(*index)?;
+    }
+    file.write_u32::<LE>(uvs.len() as u32)?;
+    for uv in uvs {
+        file.write_f32::<LE>(uv.x)?;
+        file.write_f32::<LE>(uv.y)?;
+    }
+    file.write_u32::<LE>(mats.len() as u32)?;
+    for mat in mats {
+        file.write_u32::<LE>(mat.len() as u32)?;
+        file.write_all(mat.as_bytes())?;
+    }
+    Ok(())
+}
+
+fn read_texture(file: &mut impl Read, size: u64) -> io::Result<TexData> {
+    let width = file.read_u32::<LE>()?;
+    let height = file.read_u32::<LE>()?;
+    let mut data = vec![0; count(file, size, 1)? as usize];
+    file.read_exact(&mut data)?;
+    Ok((width, height, data))
+}
+
+fn write_texture(file: &mut dyn Write, (width, height, data): &TexData) -> io::Result<()> {
+    file.write_u32::<LE>(*width)?;
+    file.write_u32::<LE>(*height)?;
+    file.write_u32::<LE>(data.len() as u32)?;
+    file.write_all(data)
+}
+
+#[test]
+fn roundtrip() {
+    let mesh: MeshData = (
+        vec![Vec3::new(1.0, 2.0, 3.0), Vec3::ZERO],
+        vec![0, 1, 0],
+        vec![Vec2::new(0.5, 0.25), Vec2::ONE],
+        vec!["/Game/Materials/M_Rock".into()],
+    );
+    let mut buf = Vec::new();
+    write_mesh(&mut buf, &mesh).unwrap();
+    assert_eq!(
+        read_mesh(&mut buf.as_slice(), buf.len() as u64).unwrap(),
+        mesh
+    );
+    let tex: TexData = (2, 1, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+    let mut buf = Vec::new();
+    write_texture(&mut buf, &tex).unwrap();
+    assert_eq!(
+        read_texture(&mut buf.as_slice(), buf.len() as u64).unwrap(),
+        tex
+    );
+    // a corrupt length mustn't allocate more than the file could hold
+    buf[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(read_texture(&mut buf.as_slice(), buf.len() as u64).is_err());
+    // cache files are named with this so it can't change
+    assert_eq!(hash(b"stove"), 0x44e144ad76a61802);
+}
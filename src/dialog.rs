@@ -7,15 +7,15 @@ pub fn open(
     commands: Commands,
     actors: Query<Entity, With<actor::Actor>>,
     mut notif: EventWriter<Notif>,
-    appdata: ResMut<AppData>,
+    mut appdata: ResMut<AppData>,
     client: ResMut<Client>,
     map: NonSendMut<Map>,
-    registry: ResMut<Registry>,
-    meshes: ResMut<Assets<Mesh>>,
-    materials: ResMut<Assets<unlit::Unlit>>,
-    images: ResMut<Assets<Image>>,
-    paks: Res<Paks>,
+    registry: Res<Registry>,
+    loading: ResMut<Loading>,
+    vfs: Res<vfs::Vfs>,
     consts: Res<Constants>,
+    origin: Res<Origin>,
+    mut detecting: ResMut<Detecting>,
 ) {
     let Some(path) = trigger.event().0.clone().or_else(|| {
         rfd::FileDialog::new()
@@ -25,20 +25,138 @@ pub fn open(
     }) else {
         return;
     };
-    let asset = match asset::open(&path, appdata.version()) {
-        Ok(asset) => asset,
+    let opened = asset::open(&path, appdata.version(), vfs.usmap.as_ref());
+    let error = match opened {
+        // the current version is usually right so only search when it parses badly
+        Ok(asset) if !appdata.detect || asset::raw_exports(&asset) == 0 => {
+            open_asset(
+                path, asset, commands, actors, notif, appdata, client, map, registry, loading, vfs,
+                consts, origin,
+            );
+            return;
+        }
+        Ok(_) => None,
+        Err(e) => Some(e.to_string()),
+    };
+    // trial parsing every version would freeze the window
+    let (file, vfs) = (path.clone(), vfs.clone());
+    let task = bevy::tasks::AsyncComputeTaskPool::get()
+        .spawn(async move { asset::detect(file, vfs.usmap.as_ref()) });
+    if appdata.detect {
+        notif.send(Notif {
+            message: "detecting engine version".into(),
+            kind: Info,
+        });
+    }
+    detecting.0 = Some((path, error.filter(|_| !appdata.detect), task));
+}
+
+/// opens the map once its engine version is detected or suggests one if it failed to open
+pub fn detected(
+    mut detecting: ResMut<Detecting>,
+    commands: Commands,
+    actors: Query<Entity, With<actor::Actor>>,
+    mut notif: EventWriter<Notif>,
+    mut appdata: ResMut<AppData>,
+    client: ResMut<Client>,
+    map: NonSendMut<Map>,
+    registry: Res<Registry>,
+    loading: ResMut<Loading>,
+    vfs: Res<vfs::Vfs>,
+    consts: Res<Constants>,
+    origin: Res<Origin>,
+) {
+    if !detecting
+        .0
+        .as_ref()
+        .is_some_and(|(.., task)| task.is_finished())
+    {
+        return;
+    }
+    let Some((path, error, task)) = detecting.0.take() else {
+        return;
+    };
+    let detected = bevy::tasks::block_on(task);
+    // detection is off so only say what might work
+    if let Some(e) = error {
+        notif.send(Notif {
+            message: match detected {
+                Some(version) => format!(
+                    "{e}\nthis map looks like {} so try that version",
+                    VERSIONS[version].1
+                ),
+                None => e,
+            },
+            kind: Error,
+        });
+        return;
+    }
+    if let Some(version) = detected.filter(|version| version != &appdata.profile().version) {
+        appdata.profile_mut().version = version;
+        notif.send(Notif {
+            message: format!("detected engine version {}", VERSIONS[version].1),
+            kind: Info,
+        });
+    }
+    match asset::open(&path, appdata.version(), vfs.usmap.as_ref()) {
+        Ok(asset) => open_asset(
+            path, asset, commands, actors, notif, appdata, client, map, registry, loading, vfs,
+            consts, origin,
+        ),
         Err(e) => {
             notif.send(Notif {
                 message: e.to_string(),
                 kind: Error,
             });
-            return;
         }
-    };
-    open_asset(
-        path, asset, commands, actors, notif, appdata, client, map, registry, meshes, materials,
-        images, paks, consts,
-    );
+    }
+}
+
+/// opens a map detecting the engine version if enabled and suggesting one on failure
+fn open_map(
+    path: &std::path::Path,
+    appdata: &mut AppData,
+    notif: &mut EventWriter<Notif>,
+    vfs: &vfs::Vfs,
+) -> Option<super::Asset> {
+    let usmap = vfs.usmap.as_ref();
+    if appdata.detect {
+        // the current version is usually right so only search when it parses badly
+        if let Some(asset) = asset::open(path, appdata.version(), usmap)
+            .ok()
+            .filter(|asset| asset::raw_exports(asset) == 0)
+        {
+            return Some(asset);
+        }
+        if let Some(version) =
+            asset::detect(path, usmap).filter(|version| version != &appdata.profile().version)
+        {
+            appdata.profile_mut().version = version;
+            notif.send(Notif {
+                message: format!("detected engine version {}", VERSIONS[version].1),
+                kind: Info,
+            });
+        }
+    }
+    match asset::open(path, appdata.version(), usmap) {
+        Ok(asset) => Some(asset),
+        Err(e) => {
+            notif.send(Notif {
+                message: match (!appdata.detect)
+                    .then(|| asset::detect(path, usmap))
+                    .flatten()
+                {
+                    Some(version) => format!(
+                        "{e}\nthis map looks like {} so try that version",
+                        VERSIONS[version].1
+                    ),
+                    None => e.to_string(),
+                },
+                kind: Error,
+            });
+            None
+        }
+    }
 }
 
 fn open_asset(
@@ -50,21 +168,26 @@ fn open_asset(
     appdata: ResMut<AppData>,
     mut client: ResMut<Client>,
     mut map: NonSendMut<Map>,
-    mut registry: ResMut<Registry>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<unlit::Unlit>>,
-    mut images: ResMut<Assets<Image>>,
-    paks: Res<Paks>,
+    registry: Res<Registry>,
+    mut loading: ResMut<Loading>,
+    vfs: Res<vfs::Vfs>,
     consts: Res<Constants>,
+    origin: Res<Origin>,
 ) {
     for actor in actors.iter() {
         commands.entity(actor).despawn_recursive();
     }
-    let cache = config()
-        .filter(|_| appdata.cache)
-        .map(|path| path.join("cache"));
+    // dropping the tasks cancels whatever the last map was still loading
+    *loading = Loading::default();
+    let cache = appdata
+        .profile()
+        .cache
+        .then(|| cache::Cache::new(appdata.profile()))
+        .flatten();
+    if let Some(cache) = cache.as_ref() {
+        cache.evict(appdata.limit.saturating_mul(1024 * 1024));
+    }
     let version = appdata.version();
-    let mut batch = std::collections::BTreeMap::<_, Vec<_>>::new();
     let mut export_names: Vec<_> = asset
         .asset_data
         .exports
@@ -83,172 +206,57 @@ fn open_asset(
             }
         };
         export_names[i.index as usize - 1] = actor.name.clone();
-        match batch.get_mut(&path) {
-            Some(vec) => vec.push(actor),
-            None => {
-                batch.insert(path, vec![actor]);
+        let transform = actor.transform(&asset, origin.0);
+        match path.as_ref().and_then(|path| registry.meshes.get(path)) {
+            Some((mesh, material)) => {
+                commands.spawn((
+                    MaterialMeshBundle {
+                        mesh: mesh.clone(),
+                        material: material
+                            .as_ref()
+                            .and_then(|mat| registry.mats.get(mat).cloned())
+                            .unwrap_or(consts.grid.clone_weak()),
+                        transform,
+                        ..default()
+                    },
+                    bevy_mod_raycast::deferred::RaycastMesh::<()>::default(),
+                    actor,
+                ));
             }
-        }
-    }
-    let keys = batch.keys().flatten().cloned().collect::<Vec<_>>();
-    std::thread::scope(|s| {
-        let threads: Vec<_> = keys
-            .into_iter()
-            .map(|path| {
-                s.spawn(|| {
-                    // capture path
-                    let path = path;
-                    match asset::get(&paks, cache.as_deref(), &path, version, |asset, _| {
-                        Ok(extras::get_mesh_info(asset)?)
-                    }) {
-                        Some((positions, indices, uvs, mats, _mat_data)) => Ok((
-                            path,
-                            Mesh::new(
-                                bevy::render::render_resource::PrimitiveTopology::TriangleList,
-                                default(),
-                            )
-                            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
-                            .with_inserted_attribute(
-                                Mesh::ATTRIBUTE_UV_0,
-                                uvs.into_iter().map(|uv| uv[0]).collect::<Vec<_>>(),
-                            )
-                            .with_inserted_indices(bevy::render::mesh::Indices::U32(indices)),
-                            mats,
-                        )),
-                        None => Err(path),
-                    }
-                })
-            })
-            .collect();
-        for thread in threads {
-            match thread.join() {
-                Ok(Ok((path, mesh, mats))) => {
-                    // hard to multithread material loading since the material might not parse
-                    let mut tex = None;
-                    if appdata.textures {
-                        'outer: for mat in mats {
-                            if registry.mats.contains_key(&mat) {
-                                break;
-                            }
-                            let Some(paths) =
-                                asset::get(&paks, cache.as_deref(), &mat, version, |mat, _| {
-                                    Ok(extras::get_tex_paths(mat))
-                                })
-                            else {
-                                continue;
-                            };
-                            for path in paths {
-                                if let Some((false, width, height, data)) = asset::get(
-                                    &paks,
-                                    cache.as_deref(),
-                                    &path,
-                                    version,
-                                    |tex, bulk| Ok(extras::get_tex_info(tex, bulk)?),
-                                ) {
-                                    tex = Some((path, Image {
-                                        data,
-                                        texture_descriptor: bevy::render::render_resource::TextureDescriptor {
-                                            label: None,
-                                            size: bevy::render::render_resource::Extent3d {
-                                                width,
-                                                height,
-                                                depth_or_array_layers: 1,
-                                            },
-                                            mip_level_count: 1,
-                                            sample_count: 1,
-                                            dimension: bevy::render::render_resource::TextureDimension::D2,
-                                            format: bevy::render::render_resource::TextureFormat::Bgra8UnormSrgb,
-                                            usage:
-                                                bevy::render::render_resource::TextureUsages::TEXTURE_BINDING,
-                                            view_formats: &[
-                                                bevy::render::render_resource::TextureFormat::Bgra8UnormSrgb,
-                                            ],
-                                        },
-                                        sampler: bevy::render::texture::ImageSampler::Descriptor(
-                                            bevy::render::texture::ImageSamplerDescriptor {
-                                                address_mode_u: bevy::render::texture::ImageAddressMode::Repeat,
-                                                address_mode_v: bevy::render::texture::ImageAddressMode::Repeat,
-                                                address_mode_w: bevy::render::texture::ImageAddressMode::Repeat,
-                                                ..default()
-                                            },
-                                        ),
-                                        ..default()
-                                    }));
-                                    break 'outer;
-                                }
-                            }
-                        }
-                    }
-                    registry.meshes.insert(
-                        path.clone(),
-                        (meshes.add(mesh), tex.as_ref().map(|(path, _)| path.clone())),
-                    );
-                    if let Some((path, tex)) = tex {
-                        registry.mats.insert(
-                            path,
-                            materials.add(unlit::Unlit {
-                                texture: images.add(tex),
-                            }),
-                        );
-                    }
-                }
-                Ok(Err(path)) => {
-                    notif.send(Notif {
-                        message: format!("couldn't find the mesh at {path}"),
-                        kind: egui_notify::ToastLevel::Warning,
-                    });
-                    batch.remove(&Some(path));
-                }
-                Err(_) => continue,
-            }
-        }
-    });
-    for (path, actors) in batch {
-        match path {
-            Some(ref path) => {
-                let (mesh, material) = &registry.meshes[path];
-                for actor in actors {
-                    commands.spawn((
-                        MaterialMeshBundle {
-                            mesh: mesh.clone_weak(),
-                            material: material
-                                .as_ref()
-                                .map(|mat| registry.mats[mat].clone_weak())
-                                .unwrap_or(consts.grid.clone_weak()),
-                            transform: actor.transform(&asset),
+            None => {
+                let mut placeholder = commands.spawn((
+                    MaterialMeshBundle {
+                        mesh: consts.cube.clone_weak(),
+                        material: consts.unselected.clone_weak(),
+                        transform,
+                        ..default()
+                    },
+                    bevy::pbr::wireframe::NoWireframe,
+                    actor,
+                ));
+                placeholder.with_children(|parent| {
+                    parent.spawn((
+                        consts.bounds.clone_weak(),
+                        SpatialBundle {
+                            visibility: Visibility::Hidden,
                             ..default()
                         },
                         bevy_mod_raycast::deferred::RaycastMesh::<()>::default(),
-                        actor,
                     ));
+                });
+                // the mesh is swapped in once it's loaded
+                if let Some(path) = path {
+                    placeholder.insert(actor::Pending(path.clone()));
+                    queue(
+                        &mut loading,
+                        &vfs,
+                        cache.clone(),
+                        path,
+                        version,
+                        appdata.textures,
+                    );
                 }
             }
-            None => {
-                for actor in actors {
-                    commands
-                        .spawn((
-                            MaterialMeshBundle {
-                                mesh: consts.cube.clone_weak(),
-                                material: consts.unselected.clone_weak(),
-                                transform: actor.transform(&asset),
-                                ..default()
-                            },
-                            bevy::pbr::wireframe::NoWireframe,
-                            actor,
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn((
-                                consts.bounds.clone_weak(),
-                                SpatialBundle {
-                                    visibility: Visibility::Hidden,
-                                    ..default()
-                                },
-                                bevy_mod_raycast::deferred::RaycastMesh::<()>::default(),
-                            ));
-                        });
-                }
-                continue;
-            }
         }
     }
     let import_names = asset
@@ -271,6 +279,245 @@ fn open_asset(
     }
 }
 
+/// starts loading a mesh unless it's already on its way
+pub fn queue(
+    loading: &mut Loading,
+    vfs: &vfs::Vfs,
+    cache: Option<cache::Cache>,
+    path: String,
+    version: unreal_asset::engine_version::EngineVersion,
+    textures: bool,
+) {
+    if loading.tasks.iter().any(|(loading, _)| loading == &path) {
+        return;
+    }
+    let task = bevy::tasks::AsyncComputeTaskPool::get().spawn(load_mesh(
+        vfs.clone(),
+        cache,
+        path.clone(),
+        version,
+        textures,
+    ));
+    loading.tasks.push((path, task));
+    loading.total += 1;
+}
+
+/// reads a mesh and the first texture of its materials off the main thread
+async fn load_mesh(
+    vfs: vfs::Vfs,
+    cache: Option<cache::Cache>,
+    path: String,
+    version: unreal_asset::engine_version::EngineVersion,
+    textures: bool,
+) -> Option<(Mesh, Option<(String, Image)>)> {
+    let (positions, indices, uvs, mats) =
+        match cache.as_ref().and_then(|cache| cache.mesh(&vfs, &path)) {
+            Some(mesh) => mesh,
+            None => {
+                let (positions, indices, uvs, mats, _mat_data) =
+                    asset::get(&vfs, &path, version, |asset, _| {
+                        Ok(extras::get_mesh_info(asset)?)
+                    })?;
+                let mesh = (
+                    positions,
+                    indices,
+                    uvs.into_iter().map(|uv| uv[0]).collect(),
+                    mats,
+                );
+                if let Some(cache) = cache.as_ref() {
+                    cache.save_mesh(&vfs, &path, &mesh);
+                }
+                mesh
+            }
+        };
+    let mesh = Mesh::new(
+        bevy::render::render_resource::PrimitiveTopology::TriangleList,
+        default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(bevy::render::mesh::Indices::U32(indices));
+    if !textures {
+        return Some((mesh, None));
+    }
+    for mat in mats {
+        let Some(paths) = asset::get(&vfs, &mat, version, |mat, _| Ok(extras::get_tex_paths(mat)))
+        else {
+            continue;
+        };
+        for path in paths {
+            let cached = cache.as_ref().and_then(|cache| cache.texture(&vfs, &path));
+            if let Some((width, height, data)) = cached.or_else(|| {
+                let tex = asset::get(&vfs, &path, version, |tex, bulk| {
+                    Ok(extras::get_tex_info(tex, bulk)?)
+                })
+                .filter(|(unsupported, ..)| !unsupported)
+                .map(|(_, width, height, data)| (width, height, data))?;
+                if let Some(cache) = cache.as_ref() {
+                    cache.save_texture(&vfs, &path, &tex);
+                }
+                Some(tex)
+            }) {
+                return Some((
+                    mesh,
+                    Some((
+                        path,
+                        Image {
+                            data,
+                            texture_descriptor: bevy::render::render_resource::TextureDescriptor {
+                                label: None,
+                                size: bevy::render::render_resource::Extent3d {
+                                    width,
+                                    height,
+                                    depth_or_array_layers: 1,
+                                },
+                                mip_level_count: 1,
+                                sample_count: 1,
+                                dimension: bevy::render::render_resource::TextureDimension::D2,
+                                format:
+                                    bevy::render::render_resource::TextureFormat::Bgra8UnormSrgb,
+                                usage:
+                                    bevy::render::render_resource::TextureUsages::TEXTURE_BINDING,
+                                view_formats: &[
+                                    bevy::render::render_resource::TextureFormat::Bgra8UnormSrgb,
+                                ],
+                            },
+                            sampler: bevy::render::texture::ImageSampler::Descriptor(
+                                bevy::render::texture::ImageSamplerDescriptor {
+                                    address_mode_u: bevy::render::texture::ImageAddressMode::Repeat,
+                                    address_mode_v: bevy::render::texture::ImageAddressMode::Repeat,
+                                    address_mode_w: bevy::render::texture::ImageAddressMode::Repeat,
+                                    ..default()
+                                },
+                            ),
+                            ..default()
+                        },
+                    )),
+                ));
+            }
+        }
+    }
+    Some((mesh, None))
+}
+
+/// swaps placeholders for their meshes as the loading tasks finish
+pub fn stream(
+    mut commands: Commands,
+    mut notif: EventWriter<Notif>,
+    mut loading: ResMut<Loading>,
+    mut registry: ResMut<Registry>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<unlit::Unlit>>,
+    mut images: ResMut<Assets<Image>>,
+    consts: Res<Constants>,
+    pending: Query<(Entity, &actor::Pending, Has<actor::Selected>)>,
+) {
+    if loading.tasks.is_empty() {
+        return;
+    }
+    let (done, tasks): (Vec<_>, Vec<_>) = std::mem::take(&mut loading.tasks)
+        .into_iter()
+        .partition(|(_, task)| task.is_finished());
+    loading.tasks = tasks;
+    for (path, task) in done {
+        let Some((mesh, tex)) = bevy::tasks::block_on(task) else {
+            notif.send(Notif {
+                message: format!("couldn't find the mesh at {path}"),
+                kind: Warning,
+            });
+            for (entity, ..) in pending.iter().filter(|(_, pending, _)| pending.0 == path) {
+                commands.entity(entity).remove::<actor::Pending>();
+            }
+            continue;
+        };
+        let tex = tex.map(|(tex, image)| {
+            if !registry.mats.contains_key(&tex) {
+                registry.sizes.insert(tex.clone(), image.data.len());
+                let material = materials.add(unlit::Unlit {
+                    texture: images.add(image),
+                });
+                registry.mats.insert(tex.clone(), material);
+            }
+            tex
+        });
+        // positions, uvs and indices
+        registry.sizes.insert(
+            path.clone(),
+            mesh.count_vertices() * 20 + mesh.indices().map_or(0, |indices| indices.len() * 4),
+        );
+        let mesh = meshes.add(mesh);
+        let material = tex
+            .as_ref()
+            .and_then(|tex| registry.mats.get(tex).cloned())
+            .unwrap_or(consts.grid.clone_weak());
+        for (entity, _, selected) in pending.iter().filter(|(_, pending, _)| pending.0 == path) {
+            let mut entity = commands.entity(entity);
+            entity
+                .despawn_descendants()
+                .remove::<(
+                    actor::Pending,
+                    Handle<wire::Wire>,
+                    bevy::pbr::wireframe::NoWireframe,
+                )>()
+                .insert((
+                    mesh.clone(),
+                    material.clone(),
+                    bevy_mod_raycast::deferred::RaycastMesh::<()>::default(),
+                ));
+            if selected {
+                entity.insert(actor::SelectedBundle::default());
+            }
+        }
+        registry.meshes.insert(path, (mesh, tex));
+    }
+    if loading.tasks.is_empty() {
+        loading.total = 0;
+    }
+}
+
+/// drops meshes and textures no actor uses once the registry gets too big
+pub fn release(
+    mut registry: ResMut<Registry>,
+    appdata: Res<AppData>,
+    placing: Res<Placing>,
+    meshes: Query<&Handle<Mesh>>,
+    mats: Query<&Handle<unlit::Unlit>>,
+) {
+    // only check after something new has loaded
+    if !registry.is_changed()
+        || (registry.sizes.values().sum::<usize>() as u64) < appdata.memory * 1024 * 1024
+    {
+        return;
+    }
+    let used_meshes: std::collections::HashSet<_> = meshes.iter().map(Handle::id).collect();
+    let used_mats: std::collections::HashSet<_> = mats.iter().map(Handle::id).collect();
+    let Registry {
+        meshes,
+        mats,
+        sizes,
+    } = registry.bypass_change_detection();
+    // dropping the registry's handle frees the asset since it's the last one
+    meshes.retain(|path, (mesh, _)| {
+        // meshes waiting to be placed aren't on an actor yet
+        let used = used_meshes.contains(&mesh.id())
+            || placing.0.iter().any(|(_, placed, _)| placed == path);
+        if !used {
+            sizes.remove(path);
+        }
+        used
+    });
+    // meshes kept for placing still need their materials once they're placed
+    let kept: std::collections::HashSet<_> =
+        meshes.values().filter_map(|(_, mat)| mat.clone()).collect();
+    mats.retain(|path, mat| {
+        let used = used_mats.contains(&mat.id()) || kept.contains(path);
+        if !used {
+            sizes.remove(path);
+        }
+        used
+    });
+}
+
 pub fn save_as(
     trigger: Trigger<triggers::SaveAs>,
     mut notif: EventWriter<Notif>,
@@ -300,7 +547,7 @@ pub fn save_as(
             const PATH: &str = "./script.bat";
             #[cfg(not(target_os = "windows"))]
             const PATH: &str = "./script.sh";
-            for line in appdata.script.lines() {
+            for line in appdata.profile().script.lines() {
                 if let Err(e) = std::fs::write(PATH, line) {
                     notif.send(Notif {
                         message: format!("failed to make save script: {e}"),
@@ -321,7 +568,7 @@ pub fn save_as(
                     }),
                 };
             }
-            if !appdata.script.is_empty() {
+            if !appdata.profile().script.is_empty() {
                 if let Err(e) = std::fs::remove_file(PATH) {
                     notif.send(Notif {
                         message: format!("failed to remove save script: {e}"),
@@ -349,18 +596,85 @@ pub fn add_pak(_: Trigger<triggers::AddPak>, mut commands: Commands, mut appdata
         .pick_folder()
         .and_then(|path| path.to_str().map(str::to_string))
     {
-        appdata.pak = Some(appdata.paks.len());
-        appdata.paks.push((path, String::new()));
+        appdata.profile_mut().paks.push((path, String::new()));
         commands.trigger(triggers::LoadPaks);
     }
 }
 
+pub fn import_profile(
+    _: Trigger<triggers::ImportProfile>,
+    mut commands: Commands,
+    mut notif: EventWriter<Notif>,
+    mut appdata: ResMut<AppData>,
+) {
+    let Some(path) = rfd::FileDialog::new()
+        .set_title("import profile")
+        .add_filter("profiles", &["ron"])
+        .pick_file()
+    else {
+        return;
+    };
+    match std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|str| ron::from_str::<Profile>(&str).map_err(|e| e.to_string()))
+    {
+        Ok(mut profile) => {
+            // profiles from newer versions may list engine versions this one doesn't
+            if profile.version >= VERSIONS.len() {
+                profile.version = 0;
+            }
+            // importing a profile exported from here shouldn't share its cache
+            if appdata.profiles.iter().any(|other| other.id == profile.id) {
+                profile.id = cache::id();
+            }
+            appdata.profile = appdata.profiles.len();
+            appdata.profiles.push(profile);
+            commands.trigger(triggers::LoadPaks);
+        }
+        Err(e) => {
+            notif.send(Notif {
+                message: format!("failed to import profile: {e}"),
+                kind: Error,
+            });
+        }
+    }
+}
+
+pub fn export_profile(
+    _: Trigger<triggers::ExportProfile>,
+    mut notif: EventWriter<Notif>,
+    appdata: Res<AppData>,
+) {
+    let Some(path) = rfd::FileDialog::new()
+        .set_title("export profile")
+        .add_filter("profiles", &["ron"])
+        .set_file_name(format!("{}.ron", appdata.profile().name))
+        .save_file()
+    else {
+        return;
+    };
+    match ron::ser::to_string_pretty(appdata.profile(), default())
+        .map_err(|e| e.to_string())
+        .and_then(|str| std::fs::write(path, str).map_err(|e| e.to_string()))
+    {
+        Ok(()) => notif.send(Notif {
+            message: "profile exported".into(),
+            kind: Success,
+        }),
+        Err(e) => notif.send(Notif {
+            message: format!("failed to export profile: {e}"),
+            kind: Error,
+        }),
+    };
+}
+
 pub fn transplant_from(
     _: Trigger<triggers::TransplantFrom>,
     mut notif: EventWriter<Notif>,
-    appdata: ResMut<AppData>,
+    mut appdata: ResMut<AppData>,
     map: NonSend<Map>,
     mut transplant: NonSendMut<Transplant>,
+    vfs: Res<vfs::Vfs>,
 ) {
     if map.0.is_none() {
         notif.send(Notif {
@@ -376,44 +690,36 @@ pub fn transplant_from(
     else {
         return;
     };
-    match asset::open(path, appdata.version()) {
-        Ok(donor) => {
-            // no need for verbose warnings here
-            let actors: Vec<_> = actor::get_actors(&donor)
-                .into_iter()
-                .filter_map(|index| {
-                    actor::Actor::new(&donor, index)
-                        .ok()
-                        .map(|(_, actor)| actor)
-                })
-                .collect();
-            let selected = Vec::with_capacity(actors.len());
-            transplant.0 = Some((donor, actors, selected));
-        }
-        Err(e) => {
-            notif.send(Notif {
-                message: e.to_string(),
-                kind: Error,
-            });
-        }
-    }
+    let Some(donor) = open_map(&path, &mut appdata, &mut notif, &vfs) else {
+        return;
+    };
+    // no need for verbose warnings here
+    let actors: Vec<_> = actor::get_actors(&donor)
+        .into_iter()
+        .filter_map(|index| {
+            actor::Actor::new(&donor, index)
+                .ok()
+                .map(|(_, actor)| actor)
+        })
+        .collect();
+    let selected = Vec::with_capacity(actors.len());
+    transplant.0 = Some((donor, actors, selected));
 }
 
 pub fn transplant_into(
     _: Trigger<triggers::TransplantInto>,
     commands: Commands,
     mut notif: EventWriter<Notif>,
-    appdata: ResMut<AppData>,
+    mut appdata: ResMut<AppData>,
     mut map: NonSendMut<Map>,
     selected: Query<&actor::Actor, With<actor::Selected>>,
     actors: Query<Entity, With<actor::Actor>>,
     client: ResMut<Client>,
-    registry: ResMut<Registry>,
-    meshes: ResMut<Assets<Mesh>>,
-    materials: ResMut<Assets<unlit::Unlit>>,
-    images: ResMut<Assets<Image>>,
-    paks: Res<Paks>,
+    registry: Res<Registry>,
+    loading: ResMut<Loading>,
+    vfs: Res<vfs::Vfs>,
     consts: Res<Constants>,
+    origin: Res<Origin>,
 ) {
     let Some((donor, ..)) = &mut map.0 else {
         notif.send(Notif {
@@ -436,15 +742,8 @@ pub fn transplant_into(
     else {
         return;
     };
-    let mut recipient = match asset::open(&path, appdata.version()) {
-        Ok(recipient) => recipient,
-        Err(e) => {
-            notif.send(Notif {
-                message: e.to_string(),
-                kind: Error,
-            });
-            return;
-        }
+    let Some(mut recipient) = open_map(&path, &mut appdata, &mut notif, &vfs) else {
+        return;
     };
     for actor in selected.iter() {
         actor.transplant(&mut recipient, donor, &mut vec![], &mut vec![]);
@@ -454,7 +753,78 @@ pub fn transplant_into(
         });
     }
     open_asset(
-        path, recipient, commands, actors, notif, appdata, client, map, registry, meshes,
-        materials, images, paks, consts,
+        path, recipient, commands, actors, notif, appdata, client, map, registry, loading, vfs,
+        consts, origin,
     );
 }
+
+pub fn extract(
+    trigger: Trigger<triggers::Extract>,
+    mut notif: EventWriter<Notif>,
+    map: NonSend<Map>,
+    appdata: Res<AppData>,
+    vfs: Res<vfs::Vfs>,
+    mut extracting: ResMut<Extracting>,
+) {
+    if extracting.0.is_some() {
+        notif.send(Notif {
+            message: "still extracting the last files".into(),
+            kind: Warning,
+        });
+        return;
+    }
+    let (paths, roots) = match trigger.event() {
+        triggers::Extract::Paths(paths) => (paths.clone(), None),
+        triggers::Extract::Map => match &map.0 {
+            Some((map, ..)) => (Vec::new(), Some(extract::packages(map))),
+            None => {
+                notif.send(Notif {
+                    message: "open a map to extract what it references".into(),
+                    kind: Warning,
+                });
+                return;
+            }
+        },
+    };
+    let Some(dest) = rfd::FileDialog::new().set_title("extract to").pick_folder() else {
+        return;
+    };
+    let (vfs, version) = (vfs.clone(), appdata.version());
+    // following imports means parsing every package along the way
+    extracting.0 = Some(bevy::tasks::AsyncComputeTaskPool::get().spawn(async move {
+        let files = vfs.files();
+        let mut selected = extract::select(&vfs, &files, &paths);
+        if let Some(roots) = roots {
+            selected.extend(extract::referenced(
+                &vfs,
+                &files,
+                extract::dependencies(&vfs, version, roots),
+            ));
+        }
+        extract::write(&vfs, &selected, &dest)
+    }));
+}
+
+pub fn extracted(mut notif: EventWriter<Notif>, mut extracting: ResMut<Extracting>) {
+    if !extracting
+        .0
+        .as_ref()
+        .is_some_and(bevy::tasks::Task::is_finished)
+    {
+        return;
+    }
+    let Some(task) = extracting.0.take() else {
+        return;
+    };
+    let (written, failed) = bevy::tasks::block_on(task);
+    notif.send(Notif {
+        message: format!("extracted {written} files"),
+        kind: Success,
+    });
+    if !failed.is_empty() {
+        notif.send(Notif {
+            message: format!("couldn't extract {} files", failed.len()),
+            kind: Warning,
+        });
+    }
+}
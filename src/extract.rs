@@ -0,0 +1,220 @@
+use super::*;
+use std::collections::BTreeSet;
+
+/// the extensions a package can be split across
+const PACKAGE: [&str; 5] = ["uasset", "umap", "uexp", "ubulk", "uptnl"];
+
+const USAGE: &str =
+    "usage: stove extract <destination> [paths or prefixes...] [--map <map>] [--profile <name>]";
+
+/// the mounted files under any of the paths along with the rest of their packages
+pub fn select(vfs: &vfs::Vfs, files: &BTreeSet<String>, paths: &[String]) -> BTreeSet<String> {
+    let mut selected = BTreeSet::new();
+    for path in paths {
+        // game paths like /Game/Meshes are looked up where they're mounted
+        let prefix = match path.starts_with('/') {
+            true => vfs.resolve(path),
+            false => path.clone(),
+        };
+        let prefix = prefix.trim_end_matches('/');
+        for file in files
+            .range(prefix.to_string()..)
+            .take_while(|file| file.starts_with(prefix))
+        {
+            let stem = file
+                .rsplit_once('.')
+                .map_or(file.as_str(), |(stem, _)| stem);
+            // only whole packages and folders so SM_Rock doesn't pull in SM_Rock2
+            let rest = &file[prefix.len()..];
+            if !prefix.is_empty() && file != prefix && stem != prefix && !rest.starts_with('/') {
+                continue;
+            }
+            selected.extend(package(files, stem));
+            selected.insert(file.clone());
+        }
+    }
+    selected
+}
+
+/// the files of the given game packages which are mounted
+pub fn referenced(
+    vfs: &vfs::Vfs,
+    files: &BTreeSet<String>,
+    packages: BTreeSet<String>,
+) -> BTreeSet<String> {
+    packages
+        .iter()
+        .flat_map(|path| package(files, &vfs.resolve(path)))
+        .collect()
+}
+
+fn package(files: &BTreeSet<String>, stem: &str) -> Vec<String> {
+    PACKAGE
+        .iter()
+        .map(|ext| format!("{stem}.{ext}"))
+        .filter(|file| files.contains(file))
+        .collect()
+}
+
+/// the packages an asset imports other than native script ones
+pub fn packages(asset: &Asset) -> Vec<String> {
+    asset
+        .imports
+        .iter()
+        .filter(|import| import.outer_index.index == 0 && import.class_name == "Package")
+        .map(|import| import.object_name.get_owned_content())
+        .filter(|name| !name.starts_with("/Script/"))
+        .collect()
+}
+
+/// every package reachable from the given ones by following their imports
+pub fn dependencies(
+    vfs: &vfs::Vfs,
+    version: unreal_asset::engine_version::EngineVersion,
+    roots: Vec<String>,
+) -> BTreeSet<String> {
+    let mut found = BTreeSet::new();
+    let mut queue = roots;
+    while let Some(path) = queue.pop() {
+        if !found.insert(path.clone()) {
+            continue;
+        }
+        // packages which don't parse are still extracted but can't be followed
+        if let Some(imports) = asset::get(vfs, &path, version, |asset, _| Ok(packages(&asset))) {
+            queue.extend(imports.into_iter().filter(|import| !found.contains(import)));
+        }
+    }
+    found
+}
+
+/// writes the files under the destination as they're laid out in the paks
+pub fn write(
+    vfs: &vfs::Vfs,
+    files: &BTreeSet<String>,
+    dest: &std::path::Path,
+) -> (usize, Vec<String>) {
+    let mut written = 0;
+    let mut failed = Vec::new();
+    for file in files {
+        // paths in a pak can't be trusted to stay inside the destination
+        let path = std::path::Path::new(file);
+        if !path
+            .components()
+            .all(|part| matches!(part, std::path::Component::Normal(_)))
+        {
+            failed.push(file.clone());
+            continue;
+        }
+        let out = dest.join(path);
+        match vfs.read(file).map(|data| {
+            out.parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::write(&out, data))
+        }) {
+            Some(Ok(())) => written += 1,
+            _ => failed.push(file.clone()),
+        }
+    }
+    (written, failed)
+}
+
+/// extracts from the paks of a saved profile without opening a window
+pub fn cli(mut args: impl Iterator<Item = String>) -> AppExit {
+    let mut appdata = persistence::headless();
+    let (mut dest, mut paths, mut map) = (None, Vec::new(), None);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--map" => match args.next() {
+                Some(arg) => map = Some(arg),
+                None => {
+                    eprintln!("{USAGE}");
+                    return AppExit::error();
+                }
+            },
+            "--profile" => {
+                let name = args.next().unwrap_or_default();
+                match appdata
+                    .profiles
+                    .iter()
+                    .position(|profile| profile.name == name)
+                {
+                    Some(i) => appdata.profile = i,
+                    None => {
+                        eprintln!("there's no profile called {name}");
+                        return AppExit::error();
+                    }
+                }
+            }
+            _ if dest.is_none() => dest = Some(std::path::PathBuf::from(arg)),
+            _ => paths.push(arg),
+        }
+    }
+    let Some(dest) = dest.filter(|_| !paths.is_empty() || map.is_some()) else {
+        eprintln!("{USAGE}");
+        return AppExit::error();
+    };
+    let version = appdata.version();
+    let vfs = vfs::Vfs::mount(appdata.profile(), |warning| eprintln!("{warning}"));
+    let files = vfs.files();
+    let mut selected = select(&vfs, &files, &paths);
+    if let Some(map) = map {
+        // maps on disk are read directly and anything else is looked up in the paks
+        let roots = match std::path::Path::new(&map).exists() {
+            true => match asset::open(&map, version, vfs.usmap.as_ref()) {
+                Ok(asset) => packages(&asset),
+                Err(e) => {
+                    eprintln!("couldn't open {map}: {e}");
+                    return AppExit::error();
+                }
+            },
+            false => vec![map],
+        };
+        selected.extend(referenced(&vfs, &files, dependencies(&vfs, version, roots)));
+    }
+    let (written, failed) = write(&vfs, &selected, &dest);
+    for file in failed.iter() {
+        eprintln!("couldn't extract {file}");
+    }
+    println!("extracted {written} files to {}", dest.display());
+    match failed.is_empty() {
+        true => AppExit::Success,
+        false => AppExit::error(),
+    }
+}
+
+#[test]
+fn companions() {
+    let files: BTreeSet<String> = [
+        "Game/Content/Meshes/SM_Rock.uasset",
+        "Game/Content/Meshes/SM_Rock.uexp",
+        "Game/Content/Meshes/SM_Rock.ubulk",
+        "Game/Content/Meshes/SM_Rock2.uasset",
+        "Game/Content/Maps/Level.umap",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect();
+    assert_eq!(
+        package(&files, "Game/Content/Meshes/SM_Rock"),
+        [
+            "Game/Content/Meshes/SM_Rock.uasset",
+            "Game/Content/Meshes/SM_Rock.uexp",
+            "Game/Content/Meshes/SM_Rock.ubulk",
+        ]
+    );
+    let vfs = vfs::Vfs::default();
+    let selected = select(&vfs, &files, &["Game/Content/Meshes/SM_Rock.uexp".into()]);
+    assert_eq!(selected.len(), 3);
+    assert_eq!(
+        select(&vfs, &files, &["Game/Content/Meshes/".into()]).len(),
+        4
+    );
+    assert_eq!(
+        select(&vfs, &files, &["Game/Content/Meshes/SM_Rock".into()]).len(),
+        3
+    );
+    assert_eq!(
+        select(&vfs, &files, &["Game/Content/Meshes".into()]).len(),
+        4
+    );
+}
@@ -47,7 +47,7 @@ pub fn shortcuts(
         return;
     }
     if keys.just_released(KeyCode::Delete) {
-        commands.trigger(triggers::Delete);
+        commands.trigger(triggers::Delete(false));
     }
     if keys.just_released(KeyCode::KeyF) {
         commands.trigger(triggers::Focus);
@@ -9,15 +9,19 @@ use egui_notify::ToastLevel::{Error, Info, Success, Warning};
 mod action;
 mod actor;
 mod asset;
+mod cache;
 mod dialog;
+mod extract;
 mod extras;
 mod input;
 mod persistence;
 mod picking;
+mod query;
 mod startup;
 mod triggers;
 mod ui;
 mod unlit;
+mod vfs;
 mod wire;
 
 type Asset = unreal_asset::Asset<Wrapper>;
@@ -38,39 +42,74 @@ struct Notif {
 #[derive(Default, Resource)]
 struct Notifs(egui_notify::Toasts);
 
+/// meshes and textures kept loaded between maps of the same game
 #[derive(Default, Resource)]
 struct Registry {
     meshes: std::collections::BTreeMap<String, (Handle<Mesh>, Option<String>)>,
     mats: std::collections::BTreeMap<String, Handle<unlit::Unlit>>,
+    /// roughly how many bytes each mesh and texture takes up
+    sizes: std::collections::BTreeMap<String, usize>,
 }
 
 #[derive(Default, Resource)]
 struct Focus(Option<Vec3>);
 
-#[derive(Default, Resource)]
-struct AppData {
-    version: usize,
+/// everything needed to work on one game
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct Profile {
+    name: String,
+    /// pak folders with the aes key needed to read them
     paks: Vec<(String, String)>,
-    pak: Option<usize>,
+    version: usize,
+    usmap: Option<String>,
     cache: bool,
+    script: String,
+    /// names the cache folder so it survives renames
+    #[serde(default = "cache::id")]
+    id: u64,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            name: "default".into(),
+            paks: Vec::new(),
+            version: 0,
+            usmap: None,
+            cache: false,
+            script: String::new(),
+            id: cache::id(),
+        }
+    }
+}
+
+#[derive(Default, Resource)]
+struct AppData {
+    profiles: Vec<Profile>,
+    profile: usize,
     textures: bool,
     wireframe: bool,
-    script: String,
     query: String,
     cap: bool,
     rate: f64,
+    detect: bool,
+    /// the most megabytes each profile's cache may use
+    limit: u64,
+    /// roughly how many megabytes of meshes and textures to keep loaded
+    memory: u64,
 }
 
-#[derive(Default, Resource)]
-struct Paks(
-    String,
-    std::path::PathBuf,
-    Vec<(std::path::PathBuf, repak::PakReader)>,
-);
-
 impl AppData {
+    fn profile(&self) -> &Profile {
+        &self.profiles[self.profile]
+    }
+
+    fn profile_mut(&mut self) -> &mut Profile {
+        &mut self.profiles[self.profile]
+    }
+
     fn version(&self) -> unreal_asset::engine_version::EngineVersion {
-        VERSIONS[self.version].0
+        VERSIONS[self.profile().version].0
     }
 }
 
@@ -90,6 +129,7 @@ enum Drag {
     Translate(Vec3),
     Scale(Vec2),
     Rotate(Vec2, Vec2),
+    Select(Vec2),
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -106,11 +146,58 @@ enum Lock {
 }
 
 #[derive(Default, Resource)]
-struct Buffer(Vec3);
+struct Buffer(bevy::math::DVec3);
+
+/// the world position rendered at the bevy origin so far-away actors don't jitter
+#[derive(Default, Resource)]
+struct Origin(bevy::math::DVec3);
 
 #[derive(Default, Resource)]
 struct Hidden(bool);
 
+/// which of the package windows are open
+#[derive(Default, Resource)]
+struct Windows {
+    exports: bool,
+    imports: bool,
+    names: bool,
+    summary: bool,
+    layers: bool,
+    content: bool,
+}
+
+/// meshes of the open map still loading in the background
+#[derive(Default, Resource)]
+struct Loading {
+    tasks: Vec<(
+        String,
+        bevy::tasks::Task<Option<(Mesh, Option<(String, Image)>)>>,
+    )>,
+    total: usize,
+}
+
+/// meshes to place once they've loaded along with the map they're for and where to put them
+#[derive(Default, Resource)]
+struct Placing(Vec<(std::path::PathBuf, String, Option<Vec3>)>);
+
+/// a map waiting on its engine version being detected along with why it failed to open if detection is off
+#[derive(Default, Resource)]
+struct Detecting(
+    Option<(
+        std::path::PathBuf,
+        Option<String>,
+        bevy::tasks::Task<Option<usize>>,
+    )>,
+);
+
+/// files being written out of the paks in the background
+#[derive(Default, Resource)]
+struct Extracting(Option<bevy::tasks::Task<(usize, Vec<String>)>>);
+
+/// the actors a delete waiting for confirmation would remove and how many references it'd null
+#[derive(Default, Resource)]
+struct Deleting(Option<(Vec<Entity>, usize)>);
+
 #[derive(Default, Resource)]
 struct Client(Option<discord_rich_presence::DiscordIpcClient>);
 
@@ -153,6 +240,10 @@ fn activity() -> discord_rich_presence::activity::Activity<'static> {
 }
 
 fn main() -> AppExit {
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some("extract") {
+        return extract::cli(args);
+    }
     App::new()
         .add_plugins((
             DefaultPlugins
@@ -193,9 +284,16 @@ fn main() -> AppExit {
         .init_resource::<Drag>()
         .init_resource::<Lock>()
         .init_resource::<Buffer>()
+        .init_resource::<Origin>()
         .init_resource::<Hidden>()
+        .init_resource::<Deleting>()
+        .init_resource::<Loading>()
+        .init_resource::<Extracting>()
+        .init_resource::<Detecting>()
+        .init_resource::<Placing>()
+        .init_resource::<Windows>()
         .init_resource::<Client>()
-        .init_resource::<Paks>()
+        .init_resource::<vfs::Vfs>()
         .insert_resource(bevy::pbr::wireframe::WireframeConfig {
             global: false,
             default_color: bevy::color::palettes::css::WHITE.into(),
@@ -225,8 +323,21 @@ fn main() -> AppExit {
                 },
                 ui::sidebar,
                 ui::notifs,
+                ui::deleting,
+                ui::exports,
+                ui::imports,
+                ui::names,
+                ui::summary,
+                ui::layers,
+                ui::content,
+                ui::loading,
+                (dialog::stream, action::place, dialog::release).chain(),
+                dialog::extracted,
+                dialog::detected,
+                picking::marquee,
                 input::shortcuts,
                 action::approach,
+                action::rebase,
             ),
         )
         // post update because egui isn't built until update
@@ -237,6 +348,9 @@ fn main() -> AppExit {
         .observe(dialog::open)
         .observe(dialog::save_as)
         .observe(dialog::add_pak)
+        .observe(dialog::import_profile)
+        .observe(dialog::export_profile)
+        .observe(dialog::extract)
         .observe(dialog::transplant_from)
         .observe(dialog::transplant_into)
         .observe(action::duplicate)
@@ -248,11 +362,15 @@ fn main() -> AppExit {
         .observe(action::fullscreen)
         .observe(action::hide)
         .observe(action::load_paks)
+        .observe(action::spawn)
+        .observe(action::replace_mesh)
+        .observe(action::reveal)
         .run()
 }
 
 use unreal_asset::engine_version::EngineVersion::*;
 
+// 5.3 and 5.4 stay unlisted until mesh, texture and level parsing is checked against packages cooked with them
 const VERSIONS: [(unreal_asset::engine_version::EngineVersion, &str); 33] = [
     (UNKNOWN, "unknown"),
     (VER_UE4_OLDEST_LOADABLE_PACKAGE, "oldest"),
@@ -7,41 +7,16 @@ pub fn load(
     mut wire: ResMut<bevy::pbr::wireframe::WireframeConfig>,
     mut windows: Query<&mut Window>,
 ) {
-    let mut appdata = AppData {
-        textures: true,
-        rate: 60.0,
-        ..default()
-    };
     let mut fullscreen = false;
-    ctx.ctx_mut().memory_mut(|storage| {
-        if let Some(config) = config()
-            .map(|config| config.join("config.ron"))
-            .and_then(|path| std::fs::read_to_string(path).ok())
-            .and_then(|str| ron::from_str::<egui::util::IdTypeMap>(&str).ok())
-        {
+    let appdata = ctx.ctx_mut().memory_mut(|storage| {
+        if let Some(config) = saved() {
             storage.data = config
         }
         let data = &mut storage.data;
-        fn retrieve<T: egui::util::id_type_map::SerializableAny>(
-            val: &mut T,
-            key: &str,
-            data: &mut egui::util::IdTypeMap,
-        ) {
-            if let Some(inner) = data.get_persisted(egui::Id::new(key)) {
-                *val = inner
-            }
-        }
-        retrieve(&mut appdata.version, "version", data);
-        retrieve(&mut appdata.paks, "paks", data);
-        retrieve(&mut appdata.pak, "pak", data);
-        retrieve(&mut appdata.cache, "cache", data);
-        retrieve(&mut appdata.textures, "textures", data);
-        retrieve(&mut appdata.wireframe, "wireframe", data);
-        retrieve(&mut appdata.script, "script", data);
-        retrieve(&mut appdata.cap, "cap", data);
-        retrieve(&mut appdata.rate, "rate", data);
         retrieve(&mut fullscreen, "fullscreen", data);
+        appdata(data)
     });
+    cache::remove_old();
     fps.limiter = match appdata.cap {
         true => bevy_framepace::Limiter::from_framerate(appdata.rate),
         false => bevy_framepace::Limiter::Off,
@@ -55,6 +30,115 @@ pub fn load(
     commands.trigger(triggers::LoadPaks);
 }
 
+/// the settings as they were last written
+fn saved() -> Option<egui::util::IdTypeMap> {
+    config()
+        .map(|config| config.join("config.ron"))
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|str| ron::from_str::<egui::util::IdTypeMap>(&str).ok())
+}
+
+fn retrieve<T: egui::util::id_type_map::SerializableAny>(
+    val: &mut T,
+    key: &str,
+    data: &mut egui::util::IdTypeMap,
+) {
+    if let Some(inner) = data.get_persisted(egui::Id::new(key)) {
+        *val = inner
+    }
+}
+
+fn appdata(data: &mut egui::util::IdTypeMap) -> AppData {
+    let mut appdata = AppData {
+        textures: true,
+        rate: 60.0,
+        detect: true,
+        limit: 1024,
+        memory: 1024,
+        ..default()
+    };
+    retrieve(&mut appdata.profiles, "profiles", data);
+    retrieve(&mut appdata.profile, "profile", data);
+    retrieve(&mut appdata.textures, "textures", data);
+    retrieve(&mut appdata.wireframe, "wireframe", data);
+    retrieve(&mut appdata.cap, "cap", data);
+    retrieve(&mut appdata.rate, "rate", data);
+    retrieve(&mut appdata.detect, "detect", data);
+    retrieve(&mut appdata.limit, "limit", data);
+    retrieve(&mut appdata.memory, "memory", data);
+    if appdata.profiles.is_empty() {
+        appdata.profiles = migrate(data);
+    }
+    if appdata.profile >= appdata.profiles.len() {
+        appdata.profile = 0;
+    }
+    // versions which are no longer listed fall back to unknown
+    for profile in appdata.profiles.iter_mut() {
+        if profile.version >= VERSIONS.len() {
+            profile.version = 0;
+        }
+    }
+    // profiles saved before they had ids get fresh ones which mustn't clash
+    let mut ids = std::collections::HashSet::new();
+    for profile in appdata.profiles.iter_mut() {
+        while !ids.insert(profile.id) {
+            profile.id = cache::id();
+        }
+    }
+    appdata
+}
+
+/// the settings for running without a window
+pub fn headless() -> AppData {
+    appdata(&mut saved().unwrap_or_default())
+}
+
+/// turns the per-pak settings of older versions into profiles
+fn migrate(data: &mut egui::util::IdTypeMap) -> Vec<Profile> {
+    fn take<T: egui::util::id_type_map::SerializableAny + Default>(
+        key: &str,
+        data: &mut egui::util::IdTypeMap,
+    ) -> T {
+        let id = egui::Id::new(key);
+        let val = data.get_persisted(id).unwrap_or_default();
+        data.remove::<T>(id);
+        val
+    }
+    let version: usize = take("version", data);
+    let paks: Vec<(String, String)> = take("paks", data);
+    let pak: Option<usize> = take("pak", data);
+    let cache: bool = take("cache", data);
+    let script: String = take("script", data);
+    let versions: std::collections::BTreeMap<String, usize> = take("versions", data);
+    let mut profiles: Vec<_> = paks
+        .into_iter()
+        .map(|(path, key)| Profile {
+            name: std::path::Path::new(&path)
+                .file_name()
+                .map_or_else(|| path.clone(), |name| name.to_string_lossy().into()),
+            version: versions.get(&path).copied().unwrap_or(version),
+            paks: vec![(path, key)],
+            usmap: None,
+            cache,
+            script: script.clone(),
+            id: cache::id(),
+        })
+        .collect();
+    // the selected pak becomes the active profile
+    if let Some(pak) = pak.filter(|pak| pak < &profiles.len()) {
+        profiles.swap(0, pak);
+    }
+    if profiles.is_empty() {
+        profiles.push(Profile {
+            version,
+            cache,
+            script,
+            ..default()
+        });
+    }
+    profiles
+}
+
 pub fn write(mut ctx: bevy_egui::EguiContexts, appdata: Res<AppData>, windows: Query<&Window>) {
     if !appdata.is_changed() {
         return;
@@ -62,15 +146,15 @@ pub fn write(mut ctx: bevy_egui::EguiContexts, appdata: Res<AppData>, windows: Q
     use egui::Id;
     ctx.ctx_mut().memory_mut(|storage| {
         let storage = &mut storage.data;
-        storage.insert_persisted(Id::new("version"), appdata.version);
-        storage.insert_persisted(Id::new("paks"), appdata.paks.clone());
-        storage.insert_persisted(Id::new("pak"), appdata.pak);
-        storage.insert_persisted(Id::new("cache"), appdata.cache);
+        storage.insert_persisted(Id::new("profiles"), appdata.profiles.clone());
+        storage.insert_persisted(Id::new("profile"), appdata.profile);
         storage.insert_persisted(Id::new("textures"), appdata.textures);
         storage.insert_persisted(Id::new("wireframe"), appdata.wireframe);
-        storage.insert_persisted(Id::new("script"), appdata.script.clone());
         storage.insert_persisted(Id::new("cap"), appdata.cap);
         storage.insert_persisted(Id::new("rate"), appdata.rate);
+        storage.insert_persisted(Id::new("detect"), appdata.detect);
+        storage.insert_persisted(Id::new("limit"), appdata.limit);
+        storage.insert_persisted(Id::new("memory"), appdata.memory);
         storage.insert_persisted(
             Id::new("fullscreen"),
             windows
@@ -1,5 +1,44 @@
 use super::*;
 
+/// selects an actor, swapping to the selected wireframe for actors without meshes
+pub fn select(
+    entity: Entity,
+    commands: &mut Commands,
+    cubes: &mut Query<&mut Handle<wire::Wire>>,
+    consts: &Constants,
+) {
+    match cubes.get_mut(entity) {
+        Ok(mut mat) => {
+            commands.entity(entity).insert(actor::Selected);
+            *mat = consts.selected.clone_weak();
+        }
+        Err(_) => {
+            commands
+                .entity(entity)
+                .insert(actor::SelectedBundle::default());
+        }
+    }
+}
+
+/// deselects an actor, swapping back to the unselected wireframe for actors without meshes
+pub fn deselect(
+    entity: Entity,
+    commands: &mut Commands,
+    cubes: &mut Query<&mut Handle<wire::Wire>>,
+    consts: &Constants,
+) {
+    commands.entity(entity).remove::<actor::Inspected>();
+    match cubes.get_mut(entity) {
+        Ok(mut mat) => {
+            commands.entity(entity).remove::<actor::Selected>();
+            *mat = consts.unselected.clone_weak();
+        }
+        Err(_) => {
+            commands.entity(entity).remove::<actor::SelectedBundle>();
+        }
+    }
+}
+
 pub fn pick(
     mut commands: Commands,
     mut drag: ResMut<Drag>,
@@ -46,17 +85,8 @@ pub fn pick(
             }
         } else if mouse.just_pressed(MouseButton::Left) {
             match parents.get(entity) {
-                Ok(parent) => {
-                    if let Ok(mut mat) = cubes.get_mut(parent.get()) {
-                        commands.entity(parent.get()).insert(actor::Selected);
-                        *mat = consts.selected.clone_weak();
-                    }
-                }
-                Err(_) => {
-                    commands
-                        .entity(entity)
-                        .insert(actor::SelectedBundle::default());
-                }
+                Ok(parent) => select(parent.get(), &mut commands, &mut cubes, &consts),
+                Err(_) => select(entity, &mut commands, &mut cubes, &consts),
             }
         }
     }
@@ -66,26 +96,115 @@ pub fn pick(
             KeyCode::ShiftRight,
             KeyCode::ControlLeft,
             KeyCode::ControlRight,
+            KeyCode::AltLeft,
+            KeyCode::AltRight,
         ])
         && matches!(drag.as_ref(), Drag::None)
     {
         for (entity, _) in selected.iter() {
-            match cubes.get_mut(entity) {
-                Ok(mut mat) => {
-                    commands.entity(entity).remove::<actor::Selected>();
-                    *mat = consts.unselected.clone_weak();
-                }
-                Err(_) => {
-                    commands.entity(entity).remove::<actor::SelectedBundle>();
-                }
+            deselect(entity, &mut commands, &mut cubes, &consts);
+        }
+    }
+    // clicking empty space starts a marquee
+    if mouse.just_pressed(MouseButton::Left)
+        && matches!(drag.as_ref(), Drag::None)
+        && camera.single().get_nearest_intersection().is_none()
+    {
+        if let Some(cursor) = window.single().cursor_position() {
+            *drag = Drag::Select(cursor)
+        }
+    }
+}
+
+/// draws the marquee and applies it to the selection on release
+pub fn marquee(
+    mut commands: Commands,
+    mut drag: ResMut<Drag>,
+    consts: Res<Constants>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    window: Query<&Window, With<bevy::window::PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    actors: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            Option<&bevy::render::primitives::Aabb>,
+            Has<actor::Selected>,
+        ),
+        With<actor::Actor>,
+    >,
+    mut cubes: Query<&mut Handle<wire::Wire>>,
+    mut ctx: bevy_egui::EguiContexts,
+) {
+    let Drag::Select(start) = drag.as_ref() else {
+        return;
+    };
+    let Some(cursor) = window.single().cursor_position() else {
+        return;
+    };
+    let rect = egui::Rect::from_two_pos(start.to_array().into(), cursor.to_array().into());
+    if !mouse.just_released(MouseButton::Left) {
+        let painter = ctx.ctx_mut().layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("marquee"),
+        ));
+        painter.rect_filled(rect, 0.0, egui::Color32::from_white_alpha(10));
+        painter.rect_stroke(rect, 0.0, (1.0, egui::Color32::from_rgb(255, 255, 128)));
+        return;
+    }
+    *drag = Drag::None;
+    // a plain click is already handled by pick
+    if rect.width() < 4.0 && rect.height() < 4.0 {
+        return;
+    }
+    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let alt = keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    let (camera, eye) = camera.single();
+    for (entity, transform, aabb, selected) in actors.iter() {
+        let aabb = aabb.copied().unwrap_or_default();
+        // project the corners which are in front of the camera to screen space
+        let mut bounds: Option<egui::Rect> = None;
+        for corner in [
+            Vec3::new(-1.0, -1.0, -1.0),
+            Vec3::new(-1.0, -1.0, 1.0),
+            Vec3::new(-1.0, 1.0, -1.0),
+            Vec3::new(-1.0, 1.0, 1.0),
+            Vec3::new(1.0, -1.0, -1.0),
+            Vec3::new(1.0, -1.0, 1.0),
+            Vec3::new(1.0, 1.0, -1.0),
+            Vec3::new(1.0, 1.0, 1.0),
+        ] {
+            let point = transform
+                .transform_point(Vec3::from(aabb.center) + Vec3::from(aabb.half_extents) * corner);
+            if let Some(point) = camera.world_to_viewport(eye, point) {
+                let point = egui::pos2(point.x, point.y);
+                bounds = Some(match bounds {
+                    Some(bounds) => bounds.union(egui::Rect::from_two_pos(point, point)),
+                    None => egui::Rect::from_two_pos(point, point),
+                });
             }
         }
+        let inside = bounds.is_some_and(|bounds| bounds.intersects(rect));
+        let keep = match (ctrl, shift, alt) {
+            (true, ..) => selected ^ inside,
+            (_, true, _) => selected || inside,
+            (.., true) => selected && !inside,
+            _ => inside,
+        };
+        match (selected, keep) {
+            (false, true) => select(entity, &mut commands, &mut cubes, &consts),
+            (true, false) => deselect(entity, &mut commands, &mut cubes, &consts),
+            _ => (),
+        }
     }
 }
 
 pub fn drag(
     mut drag: ResMut<Drag>,
     lock: Res<Lock>,
+    origin: Res<Origin>,
     window: Query<&Window, With<bevy::window::PrimaryWindow>>,
     mut map: NonSendMut<Map>,
     camera: Query<(
@@ -121,8 +240,8 @@ pub fn drag(
                     Lock::Z => offset.x = 0.0,
                     _ => (),
                 }
-                actor.add_location(map, offset);
-                transform.translation += offset;
+                actor.add_location(map, offset.as_dvec3());
+                transform.translation = (actor.location(map) - origin.0).as_vec3();
             }
             *drag = Drag::Translate(hit);
         }
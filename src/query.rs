@@ -0,0 +1,379 @@
+use unreal_asset::{
+    exports::ExportNormalTrait,
+    properties::{int_property::BytePropertyValue, Property, PropertyDataTrait},
+};
+
+/// a parsed outliner search e.g `class:PointLight and prop:Intensity>5000`
+#[derive(Debug)]
+pub enum Filter {
+    /// case-insensitive substring of the actor name
+    Name(String),
+    /// `name:` glob over the actor name
+    Glob(String),
+    /// `/pattern/` over the actor name
+    Regex(regex::Regex),
+    /// `class:` glob over the actor class
+    Class(String),
+    /// `mesh:` glob over the static mesh path or its file name
+    Mesh(String),
+    /// `prop:` comparison against any property of the actor or its components
+    Prop(String, Op, String),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Op {
+    Exists,
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Word(String),
+}
+
+fn tokenise(query: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            ch if ch.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            // regexes and quotes may contain spaces and brackets
+            '/' | '"' => {
+                chars.next();
+                let mut word = String::new();
+                if ch == '/' {
+                    word.push('/');
+                }
+                loop {
+                    match chars.next() {
+                        Some('\\') if ch == '/' && chars.peek() == Some(&'/') => {
+                            chars.next();
+                            word.push('/');
+                        }
+                        Some(end) if end == ch => break,
+                        Some(other) => word.push(other),
+                        None => return Err(format!("unclosed {ch}")),
+                    }
+                }
+                if ch == '/' {
+                    word.push('/');
+                }
+                tokens.push(Token::Word(word));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_whitespace() || ch == '(' || ch == ')' {
+                        break;
+                    }
+                    // allow quoting values e.g prop:Tag="some tag"
+                    if ch == '"' {
+                        chars.next();
+                        for ch in chars.by_ref() {
+                            if ch == '"' {
+                                break;
+                            }
+                            word.push(ch);
+                        }
+                        continue;
+                    }
+                    word.push(ch);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: std::iter::Peekable<std::vec::IntoIter<Token>>,
+}
+
+impl Parser {
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(
+            self.tokens.peek(),
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword)
+        );
+        if found {
+            self.tokens.next();
+        }
+        found
+    }
+
+    fn or(&mut self) -> Result<Filter, String> {
+        let mut filter = self.and()?;
+        while self.keyword("or") {
+            filter = Filter::Or(Box::new(filter), Box::new(self.and()?));
+        }
+        Ok(filter)
+    }
+
+    fn and(&mut self) -> Result<Filter, String> {
+        let mut filter = self.not()?;
+        loop {
+            // adjacent terms are implicitly and-ed
+            match self.tokens.peek() {
+                None | Some(Token::Close) => break,
+                Some(Token::Word(word)) if word.eq_ignore_ascii_case("or") => break,
+                _ => (),
+            }
+            self.keyword("and");
+            filter = Filter::And(Box::new(filter), Box::new(self.not()?));
+        }
+        Ok(filter)
+    }
+
+    fn not(&mut self) -> Result<Filter, String> {
+        match self.keyword("not") {
+            true => Ok(Filter::Not(Box::new(self.not()?))),
+            false => self.term(),
+        }
+    }
+
+    fn term(&mut self) -> Result<Filter, String> {
+        match self.tokens.next() {
+            Some(Token::Open) => {
+                let filter = self.or()?;
+                match self.tokens.next() {
+                    Some(Token::Close) => Ok(filter),
+                    _ => Err("expected )".into()),
+                }
+            }
+            Some(Token::Close) => Err("unexpected )".into()),
+            Some(Token::Word(word)) => Filter::term(&word),
+            None => Err("expected a search term".into()),
+        }
+    }
+}
+
+impl Filter {
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenise(query)?.into_iter().peekable(),
+        };
+        let filter = parser.or()?;
+        match parser.tokens.next() {
+            Some(_) => Err("unexpected )".into()),
+            None => Ok(filter),
+        }
+    }
+
+    fn term(word: &str) -> Result<Self, String> {
+        if word.len() > 1 && word.starts_with('/') && word.ends_with('/') {
+            return regex::RegexBuilder::new(&word[1..word.len() - 1])
+                .case_insensitive(true)
+                .build()
+                .map(Filter::Regex)
+                .map_err(|e| e.to_string());
+        }
+        let Some((key, value)) = word.split_once(':') else {
+            return Ok(Filter::Name(word.to_ascii_lowercase()));
+        };
+        match key.to_ascii_lowercase().as_str() {
+            "name" => Ok(Filter::Glob(value.into())),
+            "class" => Ok(Filter::Class(value.into())),
+            "mesh" => Ok(Filter::Mesh(value.into())),
+            "prop" => {
+                // check two character operators first
+                for (token, op) in [
+                    (">=", Op::Ge),
+                    ("<=", Op::Le),
+                    ("!=", Op::Ne),
+                    (">", Op::Gt),
+                    ("<", Op::Lt),
+                    ("=", Op::Eq),
+                ] {
+                    if let Some((name, value)) = value.split_once(token) {
+                        return Ok(Filter::Prop(name.into(), op, value.into()));
+                    }
+                }
+                Ok(Filter::Prop(value.into(), Op::Exists, String::new()))
+            }
+            _ => Ok(Filter::Name(word.to_ascii_lowercase())),
+        }
+    }
+
+    /// whether the actor satisfies the filter
+    pub fn matches(&self, actor: &crate::actor::Actor, asset: &crate::Asset) -> bool {
+        match self {
+            Filter::Name(name) => actor.name.to_ascii_lowercase().contains(name),
+            Filter::Glob(glob) => matches(glob, &actor.name),
+            Filter::Regex(regex) => regex.is_match(&actor.name),
+            Filter::Class(class) => matches(class, &actor.class),
+            Filter::Mesh(mesh) => actor.mesh.as_ref().is_some_and(|path| {
+                matches(mesh, path)
+                    || path
+                        .rsplit('/')
+                        .next()
+                        .is_some_and(|file| matches(mesh, file))
+            }),
+            Filter::Prop(name, op, value) => std::iter::once(actor.export)
+                .chain(
+                    actor
+                        .components(asset)
+                        .into_iter()
+                        .map(|i| i.index as usize - 1),
+                )
+                .filter_map(|i| asset.asset_data.exports[i].get_normal_export())
+                .flat_map(|norm| &norm.properties)
+                .any(|prop| any(prop, &mut |prop| compare(prop, name, op, value))),
+            Filter::And(a, b) => a.matches(actor, asset) && b.matches(actor, asset),
+            Filter::Or(a, b) => a.matches(actor, asset) || b.matches(actor, asset),
+            Filter::Not(filter) => !filter.matches(actor, asset),
+        }
+    }
+}
+
+/// whether any property in the tree satisfies the predicate
+fn any(prop: &Property, func: &mut impl FnMut(&Property) -> bool) -> bool {
+    if func(prop) {
+        return true;
+    }
+    match prop {
+        Property::ArrayProperty(arr) => arr.value.iter().any(|entry| any(entry, func)),
+        Property::MapProperty(map) => map.value.values().any(|val| any(val, func)),
+        Property::SetProperty(set) => set.value.value.iter().any(|entry| any(entry, func)),
+        Property::StructProperty(struc) => struc.value.iter().any(|entry| any(entry, func)),
+        _ => false,
+    }
+}
+
+fn compare(prop: &Property, name: &str, op: &Op, value: &str) -> bool {
+    if !prop
+        .get_name()
+        .get_content(|prop| prop.eq_ignore_ascii_case(name))
+    {
+        return false;
+    }
+    if op == &Op::Exists {
+        return true;
+    }
+    let number = match prop {
+        Property::BoolProperty(bool) => Some(bool.value as u8 as f64),
+        Property::UInt16Property(uint) => Some(uint.value as f64),
+        Property::UInt32Property(uint) => Some(uint.value as f64),
+        Property::UInt64Property(uint) => Some(uint.value as f64),
+        Property::FloatProperty(float) => Some(float.value.0 as f64),
+        Property::Int16Property(int) => Some(int.value as f64),
+        Property::Int64Property(int) => Some(int.value as f64),
+        Property::Int8Property(int) => Some(int.value as f64),
+        Property::IntProperty(int) => Some(int.value as f64),
+        Property::DoubleProperty(double) => Some(double.value.0),
+        Property::ByteProperty(byte) => match &byte.value {
+            BytePropertyValue::Byte(byte) => Some(*byte as f64),
+            BytePropertyValue::FName(_) => None,
+        },
+        _ => None,
+    };
+    let parsed = match value.to_ascii_lowercase().as_str() {
+        "true" => Some(1.0),
+        "false" => Some(0.0),
+        value => value.parse::<f64>().ok(),
+    };
+    if let (Some(number), Some(value)) = (number, parsed) {
+        return match op {
+            Op::Exists => true,
+            Op::Eq => number == value,
+            Op::Ne => number != value,
+            Op::Gt => number > value,
+            Op::Ge => number >= value,
+            Op::Lt => number < value,
+            Op::Le => number <= value,
+        };
+    }
+    let text = match prop {
+        Property::NameProperty(name) => Some(name.value.get_owned_content()),
+        Property::StrProperty(str) => str.value.clone(),
+        Property::TextProperty(txt) => txt.value.clone(),
+        Property::EnumProperty(enm) => enm.value.as_ref().map(|val| val.get_owned_content()),
+        Property::ByteProperty(byte) => match &byte.value {
+            BytePropertyValue::FName(name) => Some(name.get_owned_content()),
+            BytePropertyValue::Byte(_) => None,
+        },
+        _ => None,
+    };
+    match (text, op) {
+        (Some(text), Op::Eq) => matches(value, &text),
+        (Some(text), Op::Ne) => !matches(value, &text),
+        _ => false,
+    }
+}
+
+/// case-insensitive glob with `*` and `?` wildcards
+pub fn matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<_> = pattern.to_lowercase().chars().collect();
+    let text: Vec<_> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // where to resume if a star has to consume more
+    let mut star = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&ch) if ch == '?' || ch == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|ch| ch == &'*')
+}
+
+#[test]
+fn glob() {
+    assert!(matches("SM_Rock*", "sm_rock_01"));
+    assert!(matches("*light", "PointLight"));
+    assert!(matches("S?_*_0?", "SM_Crate_01"));
+    assert!(!matches("SM_Rock*", "SM_Crate"));
+    assert!(!matches("PointLight", "PointLightComponent"));
+}
+
+#[test]
+fn parse() {
+    let filter =
+        Filter::parse("class:PointLight prop:Intensity>=5000 or not (mesh:SM_Rock* /wall_\\d+/)")
+            .unwrap();
+    let Filter::Or(left, right) = filter else {
+        panic!("or should bind loosest: {filter:?}")
+    };
+    assert!(matches!(*left, Filter::And(ref class, ref prop)
+        if matches!(**class, Filter::Class(_))
+            && matches!(**prop, Filter::Prop(ref name, Op::Ge, ref value) if name == "Intensity" && value == "5000")));
+    assert!(matches!(*right, Filter::Not(ref inner) if matches!(**inner, Filter::And(..))));
+    assert!(Filter::parse("(class:Light").is_err());
+    assert!(Filter::parse("/unclosed").is_err());
+    assert!(matches!(Filter::parse("Crate").unwrap(), Filter::Name(name) if name == "crate"));
+}
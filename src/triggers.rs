@@ -3,8 +3,9 @@ use super::*;
 // actions
 #[derive(Event)]
 pub struct Duplicate;
+/// true once nulling references to the deleted actors has been confirmed
 #[derive(Event)]
-pub struct Delete;
+pub struct Delete(pub bool);
 #[derive(Event)]
 pub struct Focus;
 #[derive(Event)]
@@ -19,6 +20,21 @@ pub struct Fullscreen;
 pub struct Hide;
 #[derive(Event)]
 pub struct LoadPaks;
+/// adds a static mesh actor at a point or in front of the camera
+#[derive(Event)]
+pub struct Spawn {
+    pub mesh: String,
+    pub at: Option<Vec3>,
+}
+/// points the selected actors' static mesh components at another mesh
+#[derive(Event)]
+pub struct ReplaceMesh(pub String);
+/// selects the actor owning an export and optionally focuses it
+#[derive(Event)]
+pub struct Reveal {
+    pub export: usize,
+    pub focus: bool,
+}
 
 // dialogs
 #[derive(Event)]
@@ -28,6 +44,16 @@ pub struct SaveAs(pub bool);
 #[derive(Event)]
 pub struct AddPak;
 #[derive(Event)]
+pub struct ImportProfile;
+#[derive(Event)]
+pub struct ExportProfile;
+/// writes files under the paths or everything the open map references out of the paks
+#[derive(Event)]
+pub enum Extract {
+    Paths(Vec<String>),
+    Map,
+}
+#[derive(Event)]
 pub struct TransplantFrom;
 #[derive(Event)]
 pub struct TransplantInto;
@@ -1,19 +1,42 @@
 use super::*;
 
+mod content;
+mod exports;
+mod imports;
+mod layers;
+mod names;
+mod outliner;
+mod summary;
+
+pub use content::content;
+pub use exports::exports;
+pub use imports::imports;
+pub use layers::layers;
+pub use names::names;
+pub use summary::summary;
+
 pub fn sidebar(
     mut ctx: bevy_egui::EguiContexts,
     mut appdata: ResMut<AppData>,
-    mut paks: ResMut<Paks>,
     mut commands: Commands,
     mut notif: EventWriter<Notif>,
     mut map: NonSendMut<Map>,
     mut transplant: NonSendMut<Transplant>,
     mut wire: ResMut<bevy::pbr::wireframe::WireframeConfig>,
-    hidden: Res<Hidden>,
+    (hidden, mut windows): (Res<Hidden>, ResMut<Windows>),
     consts: Res<Constants>,
+    origin: Res<Origin>,
     mut fps: ResMut<bevy_framepace::FramepaceSettings>,
     actors: Query<(Entity, &actor::Actor)>,
-    mut selected: Query<(Entity, &actor::Actor, &mut Transform), With<actor::Selected>>,
+    mut selected: Query<
+        (
+            Entity,
+            &actor::Actor,
+            &mut Transform,
+            Option<&actor::Inspected>,
+        ),
+        With<actor::Selected>,
+    >,
     mut cubes: Query<&mut Handle<wire::Wire>>,
     matched: Query<(Entity, &actor::Actor), With<actor::Matched>>,
 ) {
@@ -58,53 +81,128 @@ pub fn sidebar(
                     commands.trigger(triggers::SaveAs(true));
                     ui.close_menu();
                 }
+                if ui
+                    .add_enabled(map.0.is_some(), egui::Button::new("extract referenced assets"))
+                    .clicked()
+                {
+                    commands.trigger(triggers::Extract::Map);
+                    ui.close_menu();
+                }
             });
-            egui::ComboBox::from_id_source("version").width(0.0)
-                .show_index(ui, &mut appdata.version, VERSIONS.len(), |i| VERSIONS[i].1.to_string());
-            let mut remove_at = None;
+            egui::ComboBox::from_id_source("version").width(0.0).show_index(
+                ui,
+                &mut appdata.profile_mut().version,
+                VERSIONS.len(),
+                |i| VERSIONS[i].1.to_string(),
+            );
             // kinda wanna split appdata into components so this isn't necessary
-            ui.menu_button("paks", |ui| {
-                for i in 0..appdata.paks.len() {
+            ui.menu_button("profile", |ui| {
+                let mut active = appdata.profile;
+                if egui::ComboBox::from_id_source("profiles")
+                    .show_index(ui, &mut active, appdata.profiles.len(), |i| {
+                        appdata.profiles[i].name.clone()
+                    })
+                    .changed()
+                {
+                    appdata.profile = active;
+                    commands.trigger(triggers::LoadPaks);
+                }
+                ui.horizontal(|ui| {
+                    ui.label("name:");
+                    ui.text_edit_singleline(&mut appdata.profile_mut().name);
+                });
+                ui.separator();
+                let mut remove_at = None;
+                let profile = appdata.profile_mut();
+                for (i, (path, key)) in profile.paks.iter_mut().enumerate() {
                     ui.horizontal(|ui| {
-                        let selected = appdata.pak == Some(i);
-                        if ui.selectable_label(selected, &appdata.paks[i].0).clicked() {
-                            appdata.pak = match selected {
-                                true => None,
-                                false => Some(i),
-                            };
-                            commands.trigger(triggers::LoadPaks);
-                        }
-                         if egui::TextEdit::singleline(&mut appdata.paks[i].1)
+                        ui.label(path.as_str());
+                        if egui::TextEdit::singleline(key)
                             .clip_text(false)
                             .hint_text("aes key if needed")
                             .desired_width(100.0)
-                            .show(ui).response.lost_focus() {
+                            .show(ui)
+                            .response
+                            .lost_focus()
+                        {
                             commands.trigger(triggers::LoadPaks);
                         }
                         if ui.button("x").clicked() {
-                            if selected {
-                                appdata.pak = None;
-                                paks.0.clear();
-                            }
-                            if let Some(pak) = appdata.pak.as_mut() {
-                                if &i < pak {
-                                    *pak -= 1;
-                                }
-                            }
                             remove_at = Some(i)
                         }
                     });
                 }
+                if let Some(i) = remove_at {
+                    profile.paks.remove(i);
+                    commands.trigger(triggers::LoadPaks);
+                }
                 if ui
                     .add(egui::Button::new("add pak folder").shortcut_text("alt + o"))
                     .clicked()
                 {
                     commands.trigger(triggers::AddPak);
                 }
+                ui.horizontal(|ui| {
+                    ui.label("mappings:");
+                    match profile.usmap.as_deref() {
+                        Some(usmap) => {
+                            ui.label(usmap);
+                            if ui.button("x").clicked() {
+                                profile.usmap = None;
+                                commands.trigger(triggers::LoadPaks);
+                            }
+                        }
+                        None => {
+                            if ui.button("choose usmap").clicked() {
+                                if let Some(usmap) = rfd::FileDialog::new()
+                                    .set_title("choose mappings")
+                                    .add_filter("mappings", &["usmap"])
+                                    .pick_file()
+                                    .and_then(|path| path.to_str().map(str::to_string))
+                                {
+                                    profile.usmap = Some(usmap);
+                                    commands.trigger(triggers::LoadPaks);
+                                }
+                            }
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("cache assets:");
+                    ui.add(egui::Checkbox::without_text(&mut profile.cache));
+                });
+                ui.label("post-save commands");
+                ui.text_edit_multiline(&mut profile.script);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("new").clicked() {
+                        let len = appdata.profiles.len();
+                        appdata.profile = len;
+                        appdata.profiles.push(Profile {
+                            name: format!("profile {}", len + 1),
+                            ..default()
+                        });
+                        commands.trigger(triggers::LoadPaks);
+                    }
+                    if ui
+                        .add_enabled(appdata.profiles.len() > 1, egui::Button::new("delete"))
+                        .clicked()
+                    {
+                        let profile = appdata.profile;
+                        let _ = cache::clear(appdata.profiles.remove(profile).id);
+                        appdata.profile = profile.saturating_sub(1);
+                        commands.trigger(triggers::LoadPaks);
+                    }
+                    if ui.button("import").clicked() {
+                        commands.trigger(triggers::ImportProfile);
+                        ui.close_menu();
+                    }
+                    if ui.button("export").clicked() {
+                        commands.trigger(triggers::ExportProfile);
+                        ui.close_menu();
+                    }
+                });
             });
-            if let Some(i) = remove_at {
-                appdata.paks.remove(i);
-            }
             ui.menu_button("options", |ui| {
                 ui.horizontal(|ui| {
                     ui.label("frame rate cap:"); 
@@ -118,6 +216,10 @@ pub fn sidebar(
                         fps.limiter = bevy_framepace::Limiter::from_framerate(appdata.rate)
                     }
                 });
+                ui.horizontal(|ui| {
+                    ui.label("detect engine version:");
+                    ui.add(egui::Checkbox::without_text(&mut appdata.detect));
+                });
                 ui.horizontal(|ui| {
                     ui.label("load textures:");
                     ui.add(egui::Checkbox::without_text(&mut appdata.textures));
@@ -129,29 +231,66 @@ pub fn sidebar(
                     }
                 });
                 ui.horizontal(|ui| {
-                    ui.label("cache assets:");
-                    ui.add(egui::Checkbox::without_text(&mut appdata.cache));
+                    ui.label("cache limit:");
+                    ui.add(
+                        egui::DragValue::new(&mut appdata.limit)
+                            .range(0..=1 << 20)
+                            .suffix(" mb"),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("memory budget:");
+                    ui.add(
+                        egui::DragValue::new(&mut appdata.memory)
+                            .range(256..=65536)
+                            .suffix(" mb"),
+                    );
                 });
-                if ui.button("clear cache").clicked() {
-                    match config() {
-                        Some(cache) => match std::fs::remove_dir_all(cache.join("cache")) {
-                            Ok(()) => notif.send(Notif {
-                                message: "cleared cache".into(),
-                                kind: egui_notify::ToastLevel::Info
-                            }),
-                            Err(e) => notif.send(Notif {
-                                message: e.to_string(),
-                                kind: egui_notify::ToastLevel::Error
-                            }),
-                        },
-                        None => notif.send(Notif {
-                            message: "cache does not exist".into(),
-                            kind: egui_notify::ToastLevel::Warning
-                        }),
-                    };
+                // walking the cache folders every frame is wasteful
+                let id = egui::Id::new("cache usage");
+                let time = ui.input(|input| input.time);
+                let usage = match ui.data(|data| data.get_temp::<(f64, Vec<u64>)>(id)) {
+                    Some((checked, usage)) if time - checked < 2.0 => usage,
+                    _ => {
+                        let usage: Vec<_> = appdata
+                            .profiles
+                            .iter()
+                            .map(|profile| cache::usage(profile.id))
+                            .collect();
+                        ui.data_mut(|data| data.insert_temp(id, (time, usage.clone())));
+                        usage
+                    }
+                };
+                for (profile, usage) in appdata.profiles.iter().zip(usage) {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "{}: {:.1} mb",
+                            profile.name,
+                            usage as f64 / 1024.0 / 1024.0
+                        ));
+                        if ui.button("clear").clicked() {
+                            match cache::clear(profile.id) {
+                                Ok(()) => notif.send(Notif {
+                                    message: format!("cleared cache for {}", profile.name),
+                                    kind: egui_notify::ToastLevel::Info
+                                }),
+                                Err(e) => notif.send(Notif {
+                                    message: e.to_string(),
+                                    kind: egui_notify::ToastLevel::Error
+                                }),
+                            };
+                            ui.data_mut(|data| data.remove::<(f64, Vec<u64>)>(id));
+                        }
+                    });
                 }
-                ui.label("post-save commands");
-                ui.text_edit_multiline(&mut appdata.script);
+            });
+            ui.menu_button("view", |ui| {
+                ui.checkbox(&mut windows.exports, "all exports");
+                ui.checkbox(&mut windows.imports, "imports");
+                ui.checkbox(&mut windows.names, "name map");
+                ui.checkbox(&mut windows.summary, "package summary");
+                ui.checkbox(&mut windows.layers, "pak layers");
+                ui.checkbox(&mut windows.content, "content browser");
             });
             ui.menu_button("help", |ui| {
                 ui.menu_button("about",|ui| {
@@ -169,91 +308,93 @@ pub fn sidebar(
         if map.0.is_none() {
             return;
         }
-        if ui.add(egui::TextEdit::singleline(&mut appdata.query).hint_text("🔎 search actors")).changed() {
-            for (entity, _) in matched.iter() {
-                commands.entity(entity).remove::<actor::Matched>();
+        let error = egui::Id::new("query error");
+        ui.horizontal(|ui| {
+            let search = ui.add(egui::TextEdit::singleline(&mut appdata.query).hint_text("🔎 search actors"))
+                .on_hover_text("class:PointLight prop:Intensity>5000 mesh:SM_Rock* name:Crate_* /regex/\ncombine with and, or, not and brackets");
+            if search.changed() {
+                for (entity, _) in matched.iter() {
+                    commands.entity(entity).remove::<actor::Matched>();
+                }
+                match query::Filter::parse(&appdata.query) {
+                    Ok(filter) => {
+                        ui.data_mut(|data| data.remove::<String>(error));
+                        if let Some((asset, ..)) = &map.0 {
+                            for (entity, actor) in actors.iter() {
+                                if filter.matches(actor, asset) {
+                                    commands.entity(entity).insert(actor::Matched);
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => ui.data_mut(|data| data.insert_temp(error, e)),
+                }
             }
-            for (entity, actor) in actors.iter() {
-                if actor.name.to_ascii_lowercase().contains(&appdata.query.to_ascii_lowercase()) {
-                    commands.entity(entity).insert(actor::Matched);
+            if ui.add_enabled(!appdata.query.is_empty(), egui::Button::new("select all")).clicked() {
+                for (entity, ..) in selected.iter() {
+                    if !matched.contains(entity) {
+                        picking::deselect(entity, &mut commands, &mut cubes, &consts);
+                    }
+                }
+                for (entity, _) in matched.iter() {
+                    if !selected.contains(entity) {
+                        picking::select(entity, &mut commands, &mut cubes, &consts);
+                    }
                 }
             }
+        });
+        if let Some(e) = ui.data(|data| data.get_temp::<String>(error)).filter(|_| !appdata.query.is_empty()) {
+            ui.colored_label(ui.visuals().error_fg_color, e);
         }
         ui.add_space(10.0);
-        egui::ScrollArea::both()
-            .id_source("actors")
-            .auto_shrink([false, true])
-            .max_height(ui.available_height() * 0.5)
-            .show_rows(
-                ui,
-                ui.text_style_height(&egui::TextStyle::Body),
-                match appdata.query.is_empty() {
-                    true => actors.iter().len(),
-                    false => matched.iter().len(),
-                },
-                |ui, range| ui.with_layout(egui::Layout::default().with_cross_justify(true), |ui| {
-                    let mut displayed: Vec<_> = match appdata.query.is_empty() {
-                        true => actors.iter().collect(),
-                        false => matched.iter().collect(),
-                    };
-                    displayed.sort_by_key(|(_, actor)| actor.export);
-                    for (entity, actor) in displayed.into_iter().skip(range.start).take(range.end - range.start) {
-                        let highlighted = selected.contains(entity);
-                        if ui.selectable_label(
-                            highlighted,
-                            &actor.display,
-                        )
-                        .on_hover_text(&actor.class)
-                        .clicked() {
-                            ui.input(|state| if !state.modifiers.shift && !state.modifiers.ctrl {
-                                for (entity, ..) in selected.iter() {
-                                    match cubes.get_mut(entity) {
-                                        Ok(mut mat) => {
-                                            commands.entity(entity).remove::<actor::Selected>();
-                                            *mat = consts.unselected.clone_weak();
-                                        },
-                                        Err(_) => {
-                                            commands.entity(entity).remove::<actor::SelectedBundle>();
-                                        },
-                                    }
-                                }
-                            });
-                            match highlighted {
-                                true => match cubes.get_mut(entity) {
-                                    Ok(mut mat) => {
-                                        commands.entity(entity).remove::<actor::Selected>();
-                                        *mat = consts.unselected.clone_weak();
-                                    },
-                                    Err(_) => {
-                                        commands.entity(entity).remove::<actor::SelectedBundle>();
-                                    },
-                                },
-                                // false if ui.input(|input| input.modifiers.shift) => todo!(),
-                                false => match cubes.get_mut(entity) {
-                                    Ok(mut mat) => {
-                                        commands.entity(entity).insert(actor::Selected);
-                                        *mat = consts.selected.clone_weak();
-                                    }
-                                    Err(_) => {
-                                        commands
-                                            .entity(entity)
-                                            .insert(actor::SelectedBundle::default());
-                                    }
-                                },
-                            };
-                        }
-                    }
-                })
-            );
+        if let Some((asset, _, export_names, _)) = &map.0 {
+            outliner::outliner(ui, &mut commands, &consts, asset, export_names, &appdata.query, &actors, &matched, &selected, &mut cubes);
+        }
         ui.add_space(10.0);
-        if let (Ok((_, actor, mut transform)), Some((map, _, exports, imports))) = (selected.get_single_mut(), &mut map.0) {
+        if !selected.is_empty() {
+            let id = egui::Id::new("replace mesh");
+            let mut mesh = ui.data(|data| data.get_temp::<String>(id)).unwrap_or_default();
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut mesh).hint_text("/Game/path/to/mesh").desired_width(200.0));
+                if ui.add_enabled(!mesh.is_empty(), egui::Button::new("replace mesh")).clicked() {
+                    commands.trigger(triggers::ReplaceMesh(mesh.clone()));
+                }
+            });
+            ui.data_mut(|data| data.insert_temp(id, mesh));
+        }
+        if let Some((map, _, exports, imports)) = &mut map.0 {
+            let mut selected: Vec<_> = selected.iter_mut().collect();
+            selected.sort_by_key(|(_, actor, ..)| actor.export);
             egui::ScrollArea::both()
                 .id_source("properties")
                 .auto_shrink([false; 2])
-                .show(ui, |ui| {
-                    actor.show(map, ui, &mut transform, &exports, &imports);
+                .show(ui, |ui| match selected.as_mut_slice() {
+                    [] => (),
+                    [(_, actor, transform, inspected)] => {
+                        match inspected.map(|inspected| inspected.0) {
+                            Some(component) => actor.show_component(map, ui, transform, origin.0, component, exports, imports),
+                            None => actor.show(map, ui, transform, origin.0, exports, imports),
+                        }
+                        ui.add_space(10.0);
+                        actor.show_references(map, ui, exports);
+                    }
+                    many => {
+                        let actors: Vec<_> = many.iter().map(|(_, actor, ..)| *actor).collect();
+                        actor::show_many(&actors, map, ui, exports, imports);
+                        for (_, actor, transform, _) in many.iter_mut() {
+                            let new = actor.transform(map, origin.0);
+                            if **transform != new {
+                                **transform = new;
+                            }
+                        }
+                    }
                 });
         }
+        let reveal = egui::Id::new("reveal");
+        if let Some((export, focus)) = ui.data_mut(|data| data.get_temp::<(usize, bool)>(reveal)) {
+            ui.data_mut(|data| data.remove::<(usize, bool)>(reveal));
+            commands.trigger(triggers::Reveal { export, focus });
+        }
     });
     let mut open = true;
     let mut transplanted = None;
@@ -292,7 +433,7 @@ pub fn sidebar(
                                     MaterialMeshBundle {
                                         mesh: consts.cube.clone_weak(),
                                         material: consts.selected.clone_weak(),
-                                        transform: actor.transform(map),
+                                        transform: actor.transform(map, origin.0),
                                         ..default()
                                     },
                                     bevy::pbr::wireframe::NoWireframe,
@@ -393,6 +534,8 @@ fn shortcuts(ui: &mut egui::Ui) {
             ("toggle fullscreen", "alt + enter"),
             ("hide ui", "h"),
             ("select", "left-click"),
+            ("box select", "left-drag"),
+            ("add / toggle / subtract", "shift / ctrl / alt"),
             ("deselect all", "escape"),
         ],
     );
@@ -412,6 +555,65 @@ fn shortcuts(ui: &mut egui::Ui) {
         ],
     );
 }
+pub fn deleting(
+    mut ctx: bevy_egui::EguiContexts,
+    mut commands: Commands,
+    mut deleting: ResMut<Deleting>,
+) {
+    let Some((_, refs)) = deleting.0 else { return };
+    egui::Window::new("delete actors")
+        .anchor(egui::Align2::CENTER_CENTER, (0.0, 0.0))
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx.ctx_mut(), |ui| {
+            ui.label(format!(
+                "{refs} references point at the actors being deleted and will be cleared"
+            ));
+            ui.horizontal(|ui| {
+                if ui.button("null references").clicked() {
+                    commands.trigger(triggers::Delete(true));
+                }
+                if ui.button("cancel").clicked() {
+                    deleting.0 = None;
+                }
+            });
+        });
+}
+
+/// progress of the meshes still loading for the open map
+pub fn loading(
+    mut ctx: bevy_egui::EguiContexts,
+    mut commands: Commands,
+    mut loading: ResMut<Loading>,
+    pending: Query<Entity, With<actor::Pending>>,
+) {
+    if loading.tasks.is_empty() {
+        return;
+    }
+    let done = loading.total - loading.tasks.len();
+    egui::Window::new("loading meshes")
+        .anchor(egui::Align2::RIGHT_BOTTOM, (-10.0, -10.0))
+        .resizable(false)
+        .collapsible(false)
+        .title_bar(false)
+        .show(ctx.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::ProgressBar::new(done as f32 / loading.total as f32)
+                        .desired_width(200.0)
+                        .text(format!("loaded {done} of {} meshes", loading.total)),
+                );
+                if ui.button("cancel").clicked() {
+                    // placeholders just stay as cubes
+                    *loading = Loading::default();
+                    for entity in pending.iter() {
+                        commands.entity(entity).remove::<actor::Pending>();
+                    }
+                }
+            });
+        });
+}
+
 pub fn notifs(
     mut notif: EventReader<Notif>,
     mut notifs: ResMut<Notifs>,
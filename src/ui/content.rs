@@ -0,0 +1,429 @@
+use super::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Default)]
+struct Folder {
+    folders: BTreeMap<String, Folder>,
+    files: Vec<String>,
+}
+
+impl Folder {
+    fn new(files: &BTreeSet<String>) -> Self {
+        let mut root = Self::default();
+        for file in files {
+            let mut folder = &mut root;
+            let mut split = file.split('/').peekable();
+            while let Some(name) = split.next() {
+                match split.peek() {
+                    Some(_) => folder = folder.folders.entry(name.to_string()).or_default(),
+                    None => folder.files.push(file.clone()),
+                }
+            }
+        }
+        root
+    }
+}
+
+enum Preview {
+    None,
+    /// the edges of the mesh projected onto the front view in the unit square
+    Mesh(Vec<[egui::Pos2; 2]>),
+    Texture(egui::TextureHandle),
+}
+
+struct Details {
+    path: String,
+    size: Option<u64>,
+    class: Option<String>,
+    preview: Preview,
+}
+
+/// the mounted files as a tree along with what's selected
+#[derive(Default)]
+pub struct Browser {
+    files: BTreeSet<String>,
+    /// the sizes of the files which are known without reading them
+    sizes: std::collections::HashMap<String, u64>,
+    tree: Folder,
+    listed: bool,
+    details: Option<Details>,
+    /// details of the selected file still being read along with its decoded texture
+    inspecting: Option<(
+        String,
+        bevy::tasks::Task<(Details, Option<egui::ColorImage>)>,
+    )>,
+}
+
+/// every file across the mounted paks and loose folders with previews of meshes and textures
+pub fn content(
+    mut commands: Commands,
+    mut ctx: bevy_egui::EguiContexts,
+    mut windows: ResMut<Windows>,
+    vfs: Res<vfs::Vfs>,
+    appdata: Res<AppData>,
+    mut browser: Local<Browser>,
+    camera: Query<&bevy_mod_raycast::deferred::RaycastSource<()>>,
+) {
+    // noted even while closed so the listing isn't stale when it's reopened
+    if vfs.is_changed() {
+        browser.listed = false;
+    }
+    if !windows.content {
+        return;
+    }
+    // listing is slow so only redo it when the paks change
+    if !browser.listed {
+        browser.files = vfs.files();
+        browser.sizes = vfs.sizes();
+        browser.tree = Folder::new(&browser.files);
+        browser.details = None;
+        browser.inspecting = None;
+        browser.listed = true;
+    }
+    let ctx = ctx.ctx_mut();
+    if browser
+        .inspecting
+        .as_ref()
+        .is_some_and(|(_, task)| task.is_finished())
+    {
+        if let Some((_, task)) = browser.inspecting.take() {
+            let (mut details, image) = bevy::tasks::block_on(task);
+            if let Some(image) = image {
+                details.preview =
+                    Preview::Texture(ctx.load_texture(&details.path, image, default()));
+            }
+            browser.details = Some(details);
+        }
+    }
+    let id = egui::Id::new("content");
+    let mut select = None;
+    egui::Window::new("content")
+        .open(&mut windows.content)
+        .default_size((400.0, 600.0))
+        .show(ctx, |ui| {
+            let mut search = ui
+                .data(|data| data.get_temp::<String>(id))
+                .unwrap_or_default();
+            ui.add(egui::TextEdit::singleline(&mut search).hint_text("search"));
+            let selected = match &browser.inspecting {
+                Some((path, _)) => Some(path.as_str()),
+                None => browser
+                    .details
+                    .as_ref()
+                    .map(|details| details.path.as_str()),
+            };
+            egui::ScrollArea::vertical()
+                .id_source("content tree")
+                .max_height(ui.available_height() * 0.6)
+                .auto_shrink([false; 2])
+                .show(ui, |ui| match search.is_empty() {
+                    true => folder(
+                        ui,
+                        &mut commands,
+                        &browser.tree,
+                        "",
+                        &browser.sizes,
+                        selected,
+                        &mut select,
+                    ),
+                    false => {
+                        let lower = search.to_lowercase();
+                        // showing every match would be too slow on huge paks
+                        for file in browser
+                            .files
+                            .iter()
+                            .filter(|file| file.to_lowercase().contains(&lower))
+                            .take(500)
+                        {
+                            if entry(ui, file, file, browser.sizes.get(file), selected) {
+                                select = Some(file.clone());
+                            }
+                        }
+                    }
+                });
+            ui.separator();
+            match (&browser.inspecting, &browser.details) {
+                (Some((path, _)), _) => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(path);
+                    });
+                }
+                (None, Some(details)) => show(ui, &mut commands, &vfs, details),
+                (None, None) => (),
+            }
+            ui.data_mut(|data| data.insert_temp(id, search));
+        });
+    if let Some(path) = select {
+        let (vfs, version) = (vfs.clone(), appdata.version());
+        // parsing and decoding big packages would freeze the window
+        let task =
+            bevy::tasks::AsyncComputeTaskPool::get().spawn(inspect(vfs, version, path.clone()));
+        browser.inspecting = Some((path, task));
+    }
+    // dropping an entry onto the viewport places it under the cursor
+    if ctx.input(|input| input.pointer.any_released()) && !ctx.is_pointer_over_area() {
+        if let Some(file) = egui::DragAndDrop::take_payload::<String>(ctx) {
+            if let Some(mesh) = file
+                .strip_suffix(".uasset")
+                .and_then(|package| vfs.unresolve(package))
+            {
+                commands.trigger(triggers::Spawn {
+                    mesh,
+                    at: camera
+                        .get_single()
+                        .ok()
+                        .and_then(|camera| camera.get_nearest_intersection())
+                        .map(|(_, data)| data.position()),
+                });
+            }
+        }
+    }
+}
+
+/// a selectable file which can be dragged out into the viewport
+fn entry(
+    ui: &mut egui::Ui,
+    file: &str,
+    name: &str,
+    size: Option<&u64>,
+    selected: Option<&str>,
+) -> bool {
+    let response = ui
+        .horizontal(|ui| {
+            let response = ui.selectable_label(selected == Some(file), name);
+            if let Some(size) = size {
+                ui.weak(bytes(*size));
+            }
+            response
+        })
+        .inner
+        .interact(egui::Sense::drag());
+    if file.ends_with(".uasset") {
+        response.dnd_set_drag_payload(file.to_string());
+    }
+    response.clicked()
+}
+
+fn folder(
+    ui: &mut egui::Ui,
+    commands: &mut Commands,
+    folder: &Folder,
+    prefix: &str,
+    sizes: &std::collections::HashMap<String, u64>,
+    selected: Option<&str>,
+    select: &mut Option<String>,
+) {
+    for (name, child) in folder.folders.iter() {
+        let prefix = format!("{prefix}{name}/");
+        ui.collapsing(name, |ui| {
+            self::folder(ui, commands, child, &prefix, sizes, selected, select)
+        })
+        .header_response
+        .context_menu(|ui| {
+            if ui.button("extract").clicked() {
+                commands.trigger(triggers::Extract::Paths(vec![prefix.clone()]));
+                ui.close_menu();
+            }
+        });
+    }
+    for file in folder.files.iter() {
+        let name = file.rsplit('/').next().unwrap_or(file);
+        if entry(ui, file, name, sizes.get(file), selected) {
+            *select = Some(file.clone());
+        }
+    }
+}
+
+fn show(ui: &mut egui::Ui, commands: &mut Commands, vfs: &vfs::Vfs, details: &Details) {
+    ui.label(&details.path);
+    if let Some(size) = details.size {
+        ui.label(bytes(size));
+    }
+    if let Some(class) = details.class.as_ref() {
+        ui.label(format!("class: {class}"));
+    }
+    if let Some(path) = vfs.unresolve(&details.path) {
+        // packages are referenced without the extension
+        let path = path
+            .rsplit_once('.')
+            .map_or(path.as_str(), |(path, _)| path);
+        ui.horizontal(|ui| {
+            ui.label(path);
+            if ui.button("copy path").clicked() {
+                ui.output_mut(|output| output.copied_text = path.to_string());
+            }
+        });
+        if details.class.as_deref() == Some("StaticMesh") {
+            ui.horizontal(|ui| {
+                if ui
+                    .button("place")
+                    .on_hover_text("copies a StaticMeshActor already in the map so the map needs one and settings like mobility carry over")
+                    .clicked()
+                {
+                    commands.trigger(triggers::Spawn {
+                        mesh: path.to_string(),
+                        at: None,
+                    });
+                }
+                if ui.button("replace selected").clicked() {
+                    commands.trigger(triggers::ReplaceMesh(path.to_string()));
+                }
+            });
+        }
+    }
+    if ui.button("extract").clicked() {
+        commands.trigger(triggers::Extract::Paths(vec![details.path.clone()]));
+    }
+    match &details.preview {
+        Preview::None => (),
+        Preview::Mesh(edges) => {
+            let (rect, _) = ui.allocate_exact_size(egui::Vec2::splat(200.0), egui::Sense::hover());
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+            let stroke = egui::Stroke::new(1.0, ui.visuals().text_color());
+            for [a, b] in edges {
+                painter.line_segment(
+                    [
+                        rect.min + a.to_vec2() * rect.size(),
+                        rect.min + b.to_vec2() * rect.size(),
+                    ],
+                    stroke,
+                );
+            }
+        }
+        Preview::Texture(tex) => {
+            ui.add(egui::Image::new(tex).max_size(egui::Vec2::splat(200.0)));
+        }
+    }
+}
+
+fn bytes(size: u64) -> String {
+    match size {
+        0..=1023 => format!("{size} bytes"),
+        1024..=1048575 => format!("{:.1} kb", size as f64 / 1024.0),
+        _ => format!("{:.1} mb", size as f64 / 1024.0 / 1024.0),
+    }
+}
+
+/// reads the class and preview of a file off the main thread
+async fn inspect(
+    vfs: vfs::Vfs,
+    version: unreal_asset::engine_version::EngineVersion,
+    path: String,
+) -> (Details, Option<egui::ColorImage>) {
+    let mut details = Details {
+        size: vfs.size(&path),
+        class: None,
+        preview: Preview::None,
+        path,
+    };
+    let Some(package) = details
+        .path
+        .strip_suffix(".uasset")
+        .and_then(|package| vfs.unresolve(package))
+    else {
+        return (details, None);
+    };
+    // headers are small enough to read for their size when the pak doesn't say
+    if details.size.is_none() {
+        details.size = vfs.read(&details.path).map(|data| data.len() as u64);
+    }
+    details.class = asset::class(&vfs, &package, version);
+    let mut image = None;
+    match details.class.as_deref() {
+        Some("StaticMesh") => {
+            if let Some((positions, indices, ..)) =
+                asset::get(&vfs, &package, version, |asset, _| {
+                    Ok(extras::get_mesh_info(asset)?)
+                })
+            {
+                details.preview = Preview::Mesh(edges(&positions, &indices));
+            }
+        }
+        Some("Texture2D") => {
+            image = asset::get(&vfs, &package, version, |tex, bulk| {
+                Ok(extras::get_tex_info(tex, bulk)?)
+            })
+            .filter(|(unsupported, ..)| !unsupported)
+            .map(|(_, width, height, data)| {
+                // textures are decoded as bgra
+                let rgba: Vec<_> = data
+                    .chunks_exact(4)
+                    .flat_map(|bgra| [bgra[2], bgra[1], bgra[0], bgra[3]])
+                    .collect();
+                egui::ColorImage::from_rgba_unmultiplied([width as usize, height as usize], &rgba)
+            });
+        }
+        _ => (),
+    }
+    (details, image)
+}
+
+/// projects the triangle edges onto the front view fitted to the unit square
+fn edges(positions: &[Vec3], indices: &[u32]) -> Vec<[egui::Pos2; 2]> {
+    let (min, max) = positions.iter().fold(
+        (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+        |(min, max), pos| {
+            (
+                min.min(Vec2::new(pos.x, pos.y)),
+                max.max(Vec2::new(pos.x, pos.y)),
+            )
+        },
+    );
+    let scale = (max - min).max_element().max(f32::EPSILON);
+    let point = |i: &u32| {
+        let pos = positions.get(*i as usize)?;
+        let pos = (Vec2::new(pos.x, pos.y) - min) / scale;
+        // egui's y goes down
+        Some(egui::pos2(pos.x, 1.0 - pos.y))
+    };
+    // too many lines makes the window stutter
+    indices
+        .chunks_exact(3)
+        .take(20000)
+        // broken meshes can index past their vertices
+        .filter_map(
+            |tri| match tri.iter().map(&point).collect::<Option<Vec<_>>>()?[..] {
+                [a, b, c] => Some([[a, b], [b, c], [c, a]]),
+                _ => None,
+            },
+        )
+        .flatten()
+        .collect()
+}
+
+#[test]
+fn tree() {
+    let files = BTreeSet::from([
+        "Game/Content/Maps/Castle.umap".to_string(),
+        "Game/Content/Maps/Castle.uexp".to_string(),
+        "Game/Content/Rock.uasset".to_string(),
+    ]);
+    let root = Folder::new(&files);
+    assert!(root.files.is_empty());
+    let content = &root.folders["Game"].folders["Content"];
+    assert_eq!(content.files, ["Game/Content/Rock.uasset"]);
+    assert_eq!(
+        content.folders["Maps"].files,
+        [
+            "Game/Content/Maps/Castle.umap",
+            "Game/Content/Maps/Castle.uexp"
+        ]
+    );
+}
+
+#[test]
+fn preview() {
+    let positions = [
+        Vec3::ZERO,
+        Vec3::new(2.0, 0.0, 5.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    ];
+    let lines = edges(&positions, &[0, 1, 2, 0, 1, 7]);
+    // the triangle indexing past the vertices is skipped
+    assert_eq!(lines.len(), 3);
+    // scaled by the longest side with y flipped
+    assert_eq!(lines[0], [egui::pos2(0.0, 1.0), egui::pos2(1.0, 1.0)]);
+    assert_eq!(lines[1], [egui::pos2(1.0, 1.0), egui::pos2(0.0, 0.5)]);
+}
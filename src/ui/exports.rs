@@ -0,0 +1,143 @@
+use super::*;
+use unreal_asset::{
+    exports::{ExportBaseTrait, ExportNormalTrait},
+    types::PackageIndex,
+};
+
+/// every export in the package whether or not it's a placed actor
+pub fn exports(
+    mut ctx: bevy_egui::EguiContexts,
+    mut windows: ResMut<Windows>,
+    mut map: NonSendMut<Map>,
+    origin: Res<Origin>,
+    mut actors: Query<(&actor::Actor, &mut Transform)>,
+) {
+    let Some((asset, _, exports, imports)) = &mut map.0 else {
+        return;
+    };
+    if !windows.exports {
+        return;
+    }
+    let id = egui::Id::new("export browser");
+    egui::Window::new("exports")
+        .open(&mut windows.exports)
+        .default_size((700.0, 500.0))
+        .show(ctx.ctx_mut(), |ui| {
+            let (mut search, mut selected) = ui
+                .data(|data| data.get_temp::<(String, Option<usize>)>(id))
+                .unwrap_or_default();
+            egui::SidePanel::left("export list")
+                .resizable(true)
+                .show_inside(ui, |ui| {
+                    ui.add(egui::TextEdit::singleline(&mut search).hint_text("name or class"));
+                    let lower = search.to_lowercase();
+                    let matching: Vec<_> = (0..exports.len())
+                        .filter(|i| {
+                            exports[*i].to_lowercase().contains(&lower)
+                                || name(
+                                    exports,
+                                    imports,
+                                    asset.asset_data.exports[*i].get_base_export().class_index,
+                                )
+                                .to_lowercase()
+                                .contains(&lower)
+                        })
+                        .collect();
+                    egui::ScrollArea::vertical()
+                        .auto_shrink([false; 2])
+                        .show_rows(
+                            ui,
+                            ui.text_style_height(&egui::TextStyle::Body),
+                            matching.len(),
+                            |ui, range| {
+                                for i in matching[range].iter().copied() {
+                                    let class =
+                                        asset.asset_data.exports[i].get_base_export().class_index;
+                                    ui.selectable_value(&mut selected, Some(i), &exports[i])
+                                        .on_hover_text(name(exports, imports, class));
+                                }
+                            },
+                        );
+                });
+            egui::CentralPanel::default().show_inside(ui, |ui| {
+                let Some(i) = selected.filter(|i| i < &exports.len()) else {
+                    ui.label("select an export to inspect it");
+                    return;
+                };
+                egui::ScrollArea::both()
+                    .id_source("export details")
+                    .auto_shrink([false; 2])
+                    .show(ui, |ui| {
+                        ui.heading(&exports[i]);
+                        let base = asset.asset_data.exports[i].get_base_export();
+                        egui::Grid::new("export info").striped(true).show(ui, |ui| {
+                            ui.label("class");
+                            ui.label(name(exports, imports, base.class_index));
+                            ui.end_row();
+                            ui.label("outer");
+                            ui.label(name(exports, imports, base.outer_index));
+                            ui.end_row();
+                            ui.label("flags");
+                            ui.label(format!("{:?}", base.object_flags));
+                            ui.end_row();
+                            ui.label("serial size");
+                            ui.label(base.serial_size.to_string());
+                            ui.end_row();
+                        });
+                        for (label, deps) in [
+                            (
+                                "create before create",
+                                &base.create_before_create_dependencies,
+                            ),
+                            (
+                                "create before serialization",
+                                &base.create_before_serialization_dependencies,
+                            ),
+                            (
+                                "serialization before create",
+                                &base.serialization_before_create_dependencies,
+                            ),
+                            (
+                                "serialization before serialization",
+                                &base.serialization_before_serialization_dependencies,
+                            ),
+                        ] {
+                            ui.collapsing(format!("{label} ({})", deps.len()), |ui| {
+                                for dep in deps {
+                                    ui.label(format!(
+                                        "{} ({})",
+                                        name(exports, imports, *dep),
+                                        dep.index
+                                    ));
+                                }
+                            });
+                        }
+                        ui.add_space(10.0);
+                        // root components can be edited here too so keep the viewport in sync
+                        let transforms = |asset: &Asset| -> Vec<_> {
+                            actors
+                                .iter()
+                                .filter(|(actor, _)| actor.root_component() == i)
+                                .map(|(actor, _)| actor.transform(asset, origin.0))
+                                .collect()
+                        };
+                        let before = transforms(asset);
+                        actor::show_export(asset, ui, i, exports, imports);
+                        // transforms are read from the root component alone so attached actors don't follow and only these can have moved
+                        if before != transforms(asset) {
+                            for (actor, mut transform) in actors.iter_mut() {
+                                let new = actor.transform(asset, origin.0);
+                                if *transform != new {
+                                    *transform = new;
+                                }
+                            }
+                        }
+                    });
+            });
+            ui.data_mut(|data| data.insert_temp(id, (search, selected)));
+        });
+}
+
+fn name<'a>(exports: &'a [String], imports: &'a [String], index: PackageIndex) -> &'a str {
+    actor::index_name(exports, imports, index.index).unwrap_or("invalid")
+}
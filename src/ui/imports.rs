@@ -0,0 +1,204 @@
+use super::*;
+use std::collections::BTreeMap;
+use unreal_asset::{
+    containers::{NameMap, SharedResource},
+    reader::archive_trait::ArchiveTrait,
+    types::{fname::FName, PackageIndex},
+    Import,
+};
+
+/// the import table as a tree of packages and the objects inside them
+pub fn imports(
+    mut ctx: bevy_egui::EguiContexts,
+    mut windows: ResMut<Windows>,
+    mut notif: EventWriter<Notif>,
+    mut map: NonSendMut<Map>,
+) {
+    let Some((asset, _, _, import_names)) = &mut map.0 else {
+        return;
+    };
+    if !windows.imports {
+        return;
+    }
+    let mut remove = None;
+    let mut add = None;
+    let mut renamed = None;
+    egui::Window::new("imports")
+        .open(&mut windows.imports)
+        .default_size((500.0, 500.0))
+        .show(ctx.ctx_mut(), |ui| {
+            if ui.button("add package").clicked() {
+                add = Some(PackageIndex::new(0));
+            }
+            let mut children = BTreeMap::<i32, Vec<usize>>::new();
+            for (i, import) in asset.imports.iter().enumerate() {
+                children
+                    .entry(import.outer_index.index)
+                    .or_default()
+                    .push(i);
+            }
+            let names = asset.get_name_map();
+            let len = asset.imports.len();
+            // roots are packages or objects outered to exports
+            let mut roots: Vec<_> = children
+                .iter()
+                .filter(|(outer, _)| **outer >= 0)
+                .flat_map(|(_, roots)| roots.iter().copied())
+                .collect();
+            // imports with a broken outer would never show up so list them at the root too
+            let mut reached = vec![false; len];
+            let mut stack = roots.clone();
+            loop {
+                while let Some(i) = stack.pop() {
+                    if std::mem::replace(&mut reached[i], true) {
+                        continue;
+                    }
+                    stack.extend(children.get(&(-(i as i32) - 1)).into_iter().flatten());
+                }
+                match reached.iter().position(|reached| !reached) {
+                    Some(i) => {
+                        roots.push(i);
+                        stack.push(i);
+                    }
+                    None => break,
+                }
+            }
+            egui::ScrollArea::both()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    for root in roots {
+                        node(
+                            ui,
+                            &mut asset.imports,
+                            &names,
+                            &children,
+                            root,
+                            len,
+                            &mut remove,
+                            &mut add,
+                            &mut renamed,
+                        );
+                    }
+                });
+        });
+    if let Some(outer) = add {
+        let mut names = asset.get_name_map();
+        let (class_package, class_name, object_name) = match outer.index {
+            0 => ("/Script/CoreUObject", "Package", "/Game/NewPackage"),
+            _ => ("/Script/CoreUObject", "Object", "NewObject"),
+        };
+        asset.imports.push(Import::new(
+            names.get_mut().add_fname(class_package),
+            names.get_mut().add_fname(class_name),
+            outer,
+            names.get_mut().add_fname(object_name),
+            false,
+        ));
+        import_names.push(object_name.to_string());
+    }
+    // the outliner and properties show imports by these names
+    if let Some(i) = renamed {
+        import_names[i] = asset.imports[i].object_name.get_owned_content();
+    }
+    if let Some(i) = remove {
+        match actor::remove_import(asset, i) {
+            Ok(nulled) => {
+                import_names.remove(i);
+                notif.send(Notif {
+                    message: format!("import removed and {nulled} references nulled"),
+                    kind: Warning,
+                });
+            }
+            Err(users) => {
+                notif.send(Notif {
+                    message: format!(
+                        "{users} exports are instances of or inside this import so it can't be removed"
+                    ),
+                    kind: Error,
+                });
+            }
+        }
+    }
+}
+
+fn node(
+    ui: &mut egui::Ui,
+    imports: &mut [Import],
+    names: &SharedResource<NameMap>,
+    children: &BTreeMap<i32, Vec<usize>>,
+    i: usize,
+    len: usize,
+    remove: &mut Option<usize>,
+    add: &mut Option<PackageIndex>,
+    renamed: &mut Option<usize>,
+) {
+    let import = &imports[i];
+    egui::CollapsingHeader::new(format!(
+        "{} ({})",
+        import.object_name.get_owned_content(),
+        import.class_name.get_owned_content()
+    ))
+    .id_source(i)
+    .show(ui, |ui| {
+        egui::Grid::new("fields").show(ui, |ui| {
+            let import = &mut imports[i];
+            ui.label("class package");
+            name(ui, names, &mut import.class_package);
+            ui.end_row();
+            ui.label("class name");
+            name(ui, names, &mut import.class_name);
+            ui.end_row();
+            ui.label("object name");
+            if name(ui, names, &mut import.object_name) {
+                *renamed = Some(i);
+            }
+            ui.end_row();
+            ui.label("outer");
+            ui.add(
+                egui::DragValue::new(&mut import.outer_index.index)
+                    .range(-(len as i32)..=i32::MAX)
+                    .speed(1.0),
+            );
+            ui.end_row();
+        });
+        ui.horizontal(|ui| {
+            if ui.button("add child").clicked() {
+                *add = Some(PackageIndex::new(-(i as i32) - 1));
+            }
+            if ui.button("remove").clicked() {
+                *remove = Some(i);
+            }
+        });
+        // an import outered to itself would nest forever
+        for child in children
+            .get(&(-(i as i32) - 1))
+            .into_iter()
+            .flatten()
+            .filter(|child| **child != i)
+        {
+            node(
+                ui, imports, names, children, *child, len, remove, add, renamed,
+            );
+        }
+    });
+}
+
+/// edits a name committing it to the name map once focus is lost returning whether it changed
+fn name(ui: &mut egui::Ui, names: &SharedResource<NameMap>, name: &mut FName) -> bool {
+    let id = ui.id().with(ui.next_auto_id());
+    let mut text = ui
+        .data(|data| data.get_temp::<String>(id))
+        .unwrap_or_else(|| name.get_owned_content());
+    let res = ui.text_edit_singleline(&mut text);
+    match res.has_focus() {
+        true => ui.data_mut(|data| data.insert_temp(id, text)),
+        false => {
+            ui.data_mut(|data| data.remove::<String>(id));
+            if res.lost_focus() && *name != text.as_str() {
+                *name = names.clone().get_mut().add_fname(&text);
+                return true;
+            }
+        }
+    }
+    false
+}
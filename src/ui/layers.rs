@@ -0,0 +1,54 @@
+use super::*;
+
+/// the mounted layers and which of them a package is read from
+pub fn layers(mut ctx: bevy_egui::EguiContexts, mut windows: ResMut<Windows>, vfs: Res<vfs::Vfs>) {
+    if !windows.layers {
+        return;
+    }
+    let id = egui::Id::new("layers");
+    egui::Window::new("layers")
+        .open(&mut windows.layers)
+        .default_size((400.0, 500.0))
+        .show(ctx.ctx_mut(), |ui| {
+            let mut search = ui
+                .data(|data| data.get_temp::<String>(id))
+                .unwrap_or_default();
+            ui.label(format!("game: {}", vfs.game));
+            ui.add(
+                egui::TextEdit::singleline(&mut search)
+                    .hint_text("package path e.g. /Game/Maps/Level"),
+            );
+            let found = match search.is_empty() {
+                true => Vec::new(),
+                false => vfs.lookup(&search),
+            };
+            if !search.is_empty() {
+                match found.is_empty() {
+                    true => ui.label(format!("{} isn't in any layer", vfs.resolve(&search))),
+                    false => ui.label(format!("found at {}", found[0].1)),
+                };
+            }
+            ui.separator();
+            egui::ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    egui::Grid::new("layers").striped(true).show(ui, |ui| {
+                        // highest priority first since that's what wins
+                        for (i, layer) in vfs.layers.iter().enumerate().rev() {
+                            ui.label(&layer.name);
+                            ui.label(match &layer.source {
+                                vfs::Source::Loose(_) => "loose".to_string(),
+                                vfs::Source::Pak(pak) => format!("{} files", pak.len),
+                            });
+                            match found.iter().position(|(layer, _)| layer == &i) {
+                                Some(0) => ui.colored_label(egui::Color32::GREEN, "used"),
+                                Some(_) => ui.colored_label(egui::Color32::YELLOW, "overridden"),
+                                None => ui.label(""),
+                            };
+                            ui.end_row();
+                        }
+                    });
+                });
+            ui.data_mut(|data| data.insert_temp(id, search));
+        });
+}
@@ -0,0 +1,74 @@
+use super::*;
+use std::collections::BTreeSet;
+use unreal_asset::reader::archive_trait::ArchiveTrait;
+
+/// the name map with names nothing seems to use flagged
+pub fn names(
+    mut ctx: bevy_egui::EguiContexts,
+    mut windows: ResMut<Windows>,
+    mut map: NonSendMut<Map>,
+) {
+    let Some((asset, ..)) = &mut map.0 else {
+        return;
+    };
+    if !windows.names {
+        return;
+    }
+    let id = egui::Id::new("name map");
+    egui::Window::new("names")
+        .open(&mut windows.names)
+        .default_size((400.0, 500.0))
+        .show(ctx.ctx_mut(), |ui| {
+            let (mut search, mut new, mut used) = ui
+                .data(|data| data.get_temp::<(String, String, Option<BTreeSet<String>>)>(id))
+                .unwrap_or_default();
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut new).hint_text("new name"));
+                let exists = asset.search_name_reference(&new).is_some();
+                if ui
+                    .add_enabled(!new.is_empty() && !exists, egui::Button::new("add"))
+                    .on_disabled_hover_text("the name is empty or already present")
+                    .clicked()
+                {
+                    asset.get_name_map().get_mut().add_fname(&new);
+                    new.clear();
+                }
+            });
+            ui.horizontal(|ui| {
+                // scanning every property is too slow to do each frame
+                if ui.button("find unused").clicked() {
+                    used = Some(actor::used_names(asset));
+                }
+                ui.add(egui::TextEdit::singleline(&mut search).hint_text("search"));
+            });
+            let names = asset.get_name_map();
+            let names = names.get_ref();
+            let lower = search.to_lowercase();
+            let matching: Vec<_> = names
+                .get_name_map_index_list()
+                .iter()
+                .enumerate()
+                .filter(|(_, name)| name.to_lowercase().contains(&lower))
+                .collect();
+            egui::ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show_rows(
+                    ui,
+                    ui.text_style_height(&egui::TextStyle::Body),
+                    matching.len(),
+                    |ui, range| {
+                        for (i, name) in matching[range].iter() {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("{i}"));
+                                ui.label(name.as_str());
+                                if used.as_ref().is_some_and(|used| !used.contains(*name)) {
+                                    ui.colored_label(ui.visuals().warn_fg_color, "unused")
+                                        .on_hover_text("nothing stove parses uses this but unparsed data still might");
+                                }
+                            });
+                        }
+                    },
+                );
+            ui.data_mut(|data| data.insert_temp(id, (search, new, used)));
+        });
+}
@@ -0,0 +1,241 @@
+use super::*;
+use std::collections::{BTreeMap, BTreeSet};
+use unreal_asset::exports::ExportBaseTrait;
+
+enum Row<'a> {
+    Actor(Entity, &'a actor::Actor),
+    Component(Entity, &'a actor::Actor, usize),
+}
+
+/// shows actors nested under their attach parents, each expanding into its components
+pub fn outliner(
+    ui: &mut egui::Ui,
+    commands: &mut Commands,
+    consts: &Constants,
+    asset: &Asset,
+    export_names: &[String],
+    query: &str,
+    actors: &Query<(Entity, &actor::Actor)>,
+    matched: &Query<(Entity, &actor::Actor), With<actor::Matched>>,
+    selected: &Query<
+        (
+            Entity,
+            &actor::Actor,
+            &mut Transform,
+            Option<&actor::Inspected>,
+        ),
+        With<actor::Selected>,
+    >,
+    cubes: &mut Query<&mut Handle<wire::Wire>>,
+) {
+    let id = egui::Id::new("outliner");
+    let mut expanded: BTreeSet<usize> = ui.data_mut(|data| data.get_temp(id)).unwrap_or_default();
+    let rows = match query.is_empty() {
+        // search results are shown flat
+        false => {
+            let mut displayed: Vec<_> = matched.iter().collect();
+            displayed.sort_by_key(|(_, actor)| actor.export);
+            displayed
+                .into_iter()
+                .map(|(entity, actor)| (0, Row::Actor(entity, actor)))
+                .collect()
+        }
+        true => {
+            let present: BTreeMap<_, _> = actors
+                .iter()
+                .map(|(_, actor)| (actor.export, actor))
+                .collect();
+            let mut children = BTreeMap::<_, Vec<_>>::new();
+            for (entity, actor) in actors.iter() {
+                children
+                    .entry(parent(asset, actor, &present))
+                    .or_default()
+                    .push((entity, actor));
+            }
+            for actors in children.values_mut() {
+                actors.sort_by_key(|(_, actor)| actor.export);
+            }
+            let mut rows = Vec::with_capacity(present.len());
+            flatten(&mut rows, None, 0, &children, &expanded, asset);
+            rows
+        }
+    };
+    egui::ScrollArea::both()
+        .id_source("actors")
+        .auto_shrink([false, true])
+        .max_height(ui.available_height() * 0.5)
+        .show_rows(
+            ui,
+            ui.text_style_height(&egui::TextStyle::Body),
+            rows.len(),
+            |ui, range| {
+                ui.with_layout(egui::Layout::default().with_cross_justify(true), |ui| {
+                    for (depth, row) in &rows[range] {
+                        ui.horizontal(|ui| {
+                            ui.add_space(*depth as f32 * ui.spacing().indent);
+                            match row {
+                                Row::Actor(entity, actor) => {
+                                    if query.is_empty() {
+                                        let open = expanded.contains(&actor.export);
+                                        if ui
+                                            .add(
+                                                egui::Label::new(match open {
+                                                    true => "⏷",
+                                                    false => "⏵",
+                                                })
+                                                .sense(egui::Sense::click()),
+                                            )
+                                            .clicked()
+                                        {
+                                            match open {
+                                                true => expanded.remove(&actor.export),
+                                                false => expanded.insert(actor.export),
+                                            };
+                                        }
+                                    }
+                                    let highlighted = selected.contains(*entity);
+                                    let inspecting = selected
+                                        .get(*entity)
+                                        .is_ok_and(|(.., inspected)| inspected.is_some());
+                                    if ui
+                                        .selectable_label(
+                                            highlighted && !inspecting,
+                                            &actor.display,
+                                        )
+                                        .on_hover_text(&actor.class)
+                                        .clicked()
+                                    {
+                                        ui.input(|state| {
+                                            if !state.modifiers.shift && !state.modifiers.ctrl {
+                                                for (other, ..) in selected.iter() {
+                                                    if !inspecting || other != *entity {
+                                                        picking::deselect(
+                                                            other, commands, cubes, consts,
+                                                        );
+                                                    }
+                                                }
+                                            }
+                                        });
+                                        match (highlighted, inspecting) {
+                                            // going back to the actor from one of its components keeps it selected
+                                            (true, true) => {
+                                                commands
+                                                    .entity(*entity)
+                                                    .remove::<actor::Inspected>();
+                                            }
+                                            (true, false) => {
+                                                picking::deselect(*entity, commands, cubes, consts)
+                                            }
+                                            (false, _) => {
+                                                picking::select(*entity, commands, cubes, consts)
+                                            }
+                                        };
+                                    }
+                                }
+                                Row::Component(entity, actor, component) => {
+                                    let mut label = export_names[*component].clone();
+                                    if *component == actor.root_component() {
+                                        label += " (root)";
+                                    }
+                                    if let Some(parent) = actor::attached_to(asset, *component) {
+                                        label += " → ";
+                                        label += &export_names[parent];
+                                    }
+                                    if ui
+                                        .selectable_label(
+                                            selected.get(*entity).is_ok_and(|(.., inspected)| {
+                                                inspected.is_some_and(|i| i.0 == *component)
+                                            }),
+                                            label,
+                                        )
+                                        .on_hover_text(
+                                            asset
+                                                .get_import(
+                                                    asset.asset_data.exports[*component]
+                                                        .get_base_export()
+                                                        .class_index,
+                                                )
+                                                .map(|import| {
+                                                    import.object_name.get_owned_content()
+                                                })
+                                                .unwrap_or_default(),
+                                        )
+                                        .clicked()
+                                    {
+                                        // the details panel can only show one component
+                                        for (other, ..) in selected.iter() {
+                                            if other != *entity {
+                                                picking::deselect(other, commands, cubes, consts);
+                                            }
+                                        }
+                                        if !selected.contains(*entity) {
+                                            picking::select(*entity, commands, cubes, consts);
+                                        }
+                                        commands
+                                            .entity(*entity)
+                                            .insert(actor::Inspected(*component));
+                                    }
+                                }
+                            }
+                        });
+                    }
+                })
+            },
+        );
+    ui.data_mut(|data| data.insert_temp(id, expanded));
+}
+
+/// gets the attach parent as long as it's loaded and doesn't loop back round
+fn parent(
+    asset: &Asset,
+    actor: &actor::Actor,
+    present: &BTreeMap<usize, &actor::Actor>,
+) -> Option<usize> {
+    let parent = actor
+        .attach_parent(asset)
+        .filter(|parent| present.contains_key(parent))?;
+    let mut visited = BTreeSet::from([actor.export]);
+    let mut current = parent;
+    while visited.insert(current) {
+        match present[&current]
+            .attach_parent(asset)
+            .filter(|parent| present.contains_key(parent))
+        {
+            Some(next) => current = next,
+            None => return Some(parent),
+        }
+    }
+    None
+}
+
+fn flatten<'a>(
+    rows: &mut Vec<(usize, Row<'a>)>,
+    parent: Option<usize>,
+    depth: usize,
+    children: &BTreeMap<Option<usize>, Vec<(Entity, &'a actor::Actor)>>,
+    expanded: &BTreeSet<usize>,
+    asset: &Asset,
+) {
+    let Some(actors) = children.get(&parent) else {
+        return;
+    };
+    for &(entity, actor) in actors {
+        rows.push((depth, Row::Actor(entity, actor)));
+        if expanded.contains(&actor.export) {
+            for component in actor.components(asset) {
+                rows.push((
+                    depth + 1,
+                    Row::Component(entity, actor, component.index as usize - 1),
+                ));
+            }
+            flatten(
+                rows,
+                Some(actor.export),
+                depth + 1,
+                children,
+                expanded,
+                asset,
+            );
+        }
+    }
+}
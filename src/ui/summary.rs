@@ -0,0 +1,137 @@
+use super::*;
+use unreal_asset::{flags::EPackageFlags, reader::archive_trait::ArchiveTrait};
+
+/// the package file summary for diagnosing maps which won't load
+pub fn summary(
+    mut ctx: bevy_egui::EguiContexts,
+    mut windows: ResMut<Windows>,
+    mut map: NonSendMut<Map>,
+) {
+    let Some((asset, ..)) = &mut map.0 else {
+        return;
+    };
+    if !windows.summary {
+        return;
+    }
+    egui::Window::new("package summary")
+        .open(&mut windows.summary)
+        .default_size((400.0, 500.0))
+        .show(ctx.ctx_mut(), |ui| {
+            egui::ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    egui::Grid::new("summary").striped(true).show(ui, |ui| {
+                        let mut row = |ui: &mut egui::Ui, label: &str, value: String| {
+                            ui.label(label);
+                            ui.label(value);
+                            ui.end_row();
+                        };
+                        row(
+                            ui,
+                            "engine version",
+                            format!("{:?}", asset.get_engine_version()),
+                        );
+                        row(
+                            ui,
+                            "legacy file version",
+                            asset.legacy_file_version.to_string(),
+                        );
+                        row(
+                            ui,
+                            "ue4 file version",
+                            format!("{:?}", asset.asset_data.object_version),
+                        );
+                        row(
+                            ui,
+                            "ue5 file version",
+                            format!("{:?}", asset.asset_data.object_version_ue5),
+                        );
+                        row(
+                            ui,
+                            "licensee version",
+                            asset.asset_data.summary.file_licensee_version.to_string(),
+                        );
+                        row(
+                            ui,
+                            "mappings",
+                            asset.asset_data.mappings.is_some().to_string(),
+                        );
+                        row(
+                            ui,
+                            "event driven loader",
+                            asset.asset_data.use_event_driven_loader.to_string(),
+                        );
+                        row(
+                            ui,
+                            "bulk data start offset",
+                            asset.bulk_data_start_offset.to_string(),
+                        );
+                        row(ui, "exports", asset.asset_data.exports.len().to_string());
+                        row(ui, "imports", asset.imports.len().to_string());
+                        ui.label("package flags");
+                        // only flags are editable since everything else changes how the file is read
+                        let flags = &mut asset.asset_data.summary.package_flags;
+                        let mut bits = flags.bits();
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut bits)
+                                    .hexadecimal(8, false, true)
+                                    .prefix("0x"),
+                            )
+                            .on_hover_text("bits which aren't a known flag are rejected")
+                            .changed()
+                        {
+                            if let Some(new) = EPackageFlags::from_bits(bits) {
+                                *flags = new;
+                            }
+                        }
+                        ui.end_row();
+                        ui.label("");
+                        ui.label(format!("{flags:?}"));
+                        ui.end_row();
+                    });
+                    ui.collapsing(
+                        format!(
+                            "custom versions ({})",
+                            asset.asset_data.summary.custom_versions.len()
+                        ),
+                        |ui| {
+                            egui::Grid::new("custom versions")
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for version in asset.asset_data.summary.custom_versions.iter() {
+                                        ui.label(
+                                            version.friendly_name.as_deref().unwrap_or("unknown"),
+                                        );
+                                        ui.label(version.version.to_string());
+                                        ui.label(
+                                            egui::RichText::new(
+                                                version
+                                                    .guid
+                                                    .0
+                                                    .iter()
+                                                    .map(|byte| format!("{byte:02X}"))
+                                                    .collect::<String>(),
+                                            )
+                                            .monospace(),
+                                        );
+                                        ui.end_row();
+                                    }
+                                });
+                        },
+                    );
+                    ui.collapsing(format!("generations ({})", asset.generations.len()), |ui| {
+                        egui::Grid::new("generations").striped(true).show(ui, |ui| {
+                            ui.label("exports");
+                            ui.label("names");
+                            ui.end_row();
+                            for generation in asset.generations.iter() {
+                                ui.label(generation.export_count.to_string());
+                                ui.label(generation.name_count.to_string());
+                                ui.end_row();
+                            }
+                        });
+                    });
+                });
+        });
+}
@@ -0,0 +1,568 @@
+use super::*;
+
+/// where the files of a layer are read from
+pub enum Source {
+    Loose(std::path::PathBuf),
+    Pak(Pak),
+}
+
+/// a pak file which is only opened once something is read from it
+pub struct Pak {
+    pub path: std::path::PathBuf,
+    key: Option<aes::Aes256>,
+    reader: std::sync::OnceLock<Option<repak::PakReader>>,
+    /// how many files the pak contains
+    pub len: usize,
+    size: u64,
+    modified: u64,
+}
+
+impl Pak {
+    pub fn reader(&self) -> Option<&repak::PakReader> {
+        self.reader
+            .get_or_init(|| read(&self.path, &self.key))
+            .as_ref()
+    }
+}
+
+pub struct Layer {
+    pub name: String,
+    pub source: Source,
+}
+
+/// the entries of a pak saved so it only needs reading again once it changes
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Index {
+    path: std::path::PathBuf,
+    size: u64,
+    modified: u64,
+    mount: String,
+    entries: Vec<String>,
+    /// the uncompressed size of each entry
+    #[serde(default)]
+    sizes: Vec<u64>,
+}
+
+/// the mounted pak folders shared with the loading tasks
+#[derive(Clone, Default, Resource)]
+pub struct Vfs(std::sync::Arc<Mounted>);
+
+impl std::ops::Deref for Vfs {
+    type Target = Mounted;
+
+    fn deref(&self) -> &Mounted {
+        &self.0
+    }
+}
+
+/// the pak folders of a profile mounted on top of each other
+#[derive(Default)]
+pub struct Mounted {
+    pub game: String,
+    /// lowest priority first
+    pub layers: Vec<Layer>,
+    /// the layers and entries of every lowercased mounted path in the paks lowest priority first
+    index: std::collections::HashMap<String, Vec<(usize, String)>>,
+    /// the spelling and size of every lowercased mounted path in the paks from the highest priority pak containing it
+    packed: std::collections::HashMap<String, (String, u64)>,
+    /// the content folder of each plugin
+    plugins: std::collections::BTreeMap<String, String>,
+    pub usmap: Option<asset::Mappings>,
+}
+
+impl Vfs {
+    /// mounts every pak folder of a profile with later folders and patch paks taking priority
+    pub fn mount(profile: &Profile, mut warn: impl FnMut(String)) -> Self {
+        let mut vfs = Mounted::default();
+        // parsed once here rather than for every package read
+        if let Some(usmap) = profile.usmap.as_ref() {
+            match asset::mappings(usmap) {
+                Ok(mappings) => vfs.usmap = Some(mappings),
+                Err(e) => warn(format!("couldn't read mappings {usmap}: {e}")),
+            }
+        }
+        for (path, key) in profile.paks.iter() {
+            use aes::cipher::KeyInit;
+            let key = match hex::decode(key.trim_start_matches("0x")) {
+                Ok(hex) if !key.is_empty() => aes::Aes256::new_from_slice(&hex).ok(),
+                Ok(_) => None,
+                Err(_) => {
+                    warn(format!("aes key for {path} is invalid hex"));
+                    None
+                }
+            };
+            let Ok(files) = std::fs::read_dir(path) else {
+                warn(format!("couldn't read pak folder {path}"));
+                continue;
+            };
+            let mut paks: Vec<_> = files
+                .filter_map(Result::ok)
+                .map(|dir| dir.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "pak"))
+                .map(|path| (priority(&path), path))
+                .collect();
+            paks.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (_, path) in paks {
+                let Some((index, reader)) = index(&path, &key) else {
+                    continue;
+                };
+                let layer = vfs.layers.len();
+                let mount = index
+                    .mount
+                    .trim_start_matches("../../../")
+                    .trim_start_matches('/');
+                let (len, size, modified) = (index.entries.len(), index.size, index.modified);
+                for (entry, entry_size) in index.entries.into_iter().zip(index.sizes) {
+                    vfs.add(layer, mount.to_string() + &entry, entry, entry_size);
+                }
+                let cell = std::sync::OnceLock::new();
+                if let Some(reader) = reader {
+                    let _ = cell.set(Some(reader));
+                }
+                vfs.layers.push(Layer {
+                    name: path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into())
+                        .unwrap_or_default(),
+                    source: Source::Pak(Pak {
+                        path,
+                        key: key.clone(),
+                        reader: cell,
+                        len,
+                        size,
+                        modified,
+                    }),
+                });
+            }
+            // loose files override the paks next to them
+            vfs.layers.push(Layer {
+                name: format!("{path} (loose)"),
+                source: Source::Loose(path.into()),
+            });
+        }
+        vfs.game = vfs.detect_game().unwrap_or_default();
+        Self(std::sync::Arc::new(vfs))
+    }
+}
+
+impl Mounted {
+    /// adds a pak entry under its mounted path
+    fn add(&mut self, layer: usize, mounted: String, entry: String, size: u64) {
+        if let Some((plugin, content)) = plugin(&mounted) {
+            self.plugins.entry(plugin).or_insert(content);
+        }
+        // unreal paths aren't case sensitive so neither are lookups
+        let key = mounted.to_lowercase();
+        self.index
+            .entry(key.clone())
+            .or_default()
+            .push((layer, entry));
+        self.packed.insert(key, (mounted, size));
+    }
+
+    /// turns a /Game, /Engine or plugin path into its mounted path
+    pub fn resolve(&self, path: &str) -> String {
+        let path = path.trim_start_matches('/');
+        let (root, rest) = path.split_once('/').unwrap_or((path, ""));
+        match root {
+            "Game" => format!("{}/Content/{rest}", self.game),
+            "Engine" => format!("Engine/Content/{rest}"),
+            plugin => match self.plugins.get(plugin) {
+                Some(content) => format!("{content}/{rest}"),
+                None => format!("{plugin}/Content/{rest}"),
+            },
+        }
+    }
+
+    /// turns a mounted path back into the /Game, /Engine or plugin path used to reference it
+    pub fn unresolve(&self, mounted: &str) -> Option<String> {
+        let (content, rest) = mounted.split_once("/Content/")?;
+        let root = match content {
+            game if game == self.game => "Game",
+            "Engine" => "Engine",
+            plugin => plugin.rsplit('/').next()?,
+        };
+        Some(format!("/{root}/{rest}"))
+    }
+
+    /// every mounted path across the paks and loose folders
+    pub fn files(&self) -> std::collections::BTreeSet<String> {
+        self.sizes().into_keys().collect()
+    }
+
+    /// the size of every mounted file with loose files taking priority over the paks
+    pub fn sizes(&self) -> std::collections::HashMap<String, u64> {
+        fn walk(
+            root: &std::path::Path,
+            dir: &std::path::Path,
+            files: &mut std::collections::HashMap<String, (String, u64)>,
+        ) {
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                return;
+            };
+            for entry in entries.filter_map(Result::ok) {
+                let path = entry.path();
+                match entry.file_type().is_ok_and(|t| t.is_dir()) {
+                    true => walk(root, &path, files),
+                    // the paks, signatures and io store containers beside them aren't content
+                    false if dir == root => (),
+                    false => {
+                        if let (Ok(relative), Ok(meta)) =
+                            (path.strip_prefix(root), entry.metadata())
+                        {
+                            let relative = relative.to_string_lossy().replace('\\', "/");
+                            files.insert(relative.to_lowercase(), (relative, meta.len()));
+                        }
+                    }
+                }
+            }
+        }
+        // later layers take priority so overwrite what's beneath them
+        let mut files = self.packed.clone();
+        for layer in self.layers.iter() {
+            if let Source::Loose(root) = &layer.source {
+                walk(root, root, &mut files);
+            }
+        }
+        files.into_values().collect()
+    }
+
+    /// the size of a file from the loose file's metadata or the pak index
+    pub fn size(&self, mounted: &str) -> Option<u64> {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|layer| match &layer.source {
+                Source::Loose(root) => std::fs::metadata(root.join(mounted))
+                    .ok()
+                    .map(|meta| meta.len()),
+                Source::Pak(_) => None,
+            })
+            .or_else(|| {
+                self.packed
+                    .get(&mounted.to_lowercase())
+                    .map(|(_, size)| *size)
+            })
+    }
+
+    /// the raw bytes of a mounted path from the highest priority layer containing it
+    pub fn read(&self, mounted: &str) -> Option<Vec<u8>> {
+        let loose =
+            self.layers
+                .iter()
+                .enumerate()
+                .rev()
+                .find_map(|(i, layer)| match &layer.source {
+                    Source::Loose(root) => root.join(mounted).exists().then_some(i),
+                    Source::Pak(_) => None,
+                });
+        let packed = self
+            .index
+            .get(&mounted.to_lowercase())
+            .and_then(|layers| layers.last())
+            .filter(|(i, _)| loose.map_or(true, |loose| *i > loose));
+        match (packed, loose) {
+            (Some((i, entry)), _) => {
+                let Source::Pak(pak) = &self.layers[*i].source else {
+                    return None;
+                };
+                let mut file = std::io::BufReader::new(std::fs::File::open(&pak.path).ok()?);
+                pak.reader()?.get(entry, &mut file).ok()
+            }
+            (None, Some(i)) => {
+                let Source::Loose(root) = &self.layers[i].source else {
+                    return None;
+                };
+                std::fs::read(root.join(mounted)).ok()
+            }
+            (None, None) => None,
+        }
+    }
+
+    /// every layer containing a package from highest priority with its path in that layer
+    pub fn lookup(&self, path: &str) -> Vec<(usize, String)> {
+        let path = self.resolve(path);
+        let file = path.clone() + ".uasset";
+        let mut found: Vec<_> = self
+            .index
+            .get(&file.to_lowercase())
+            .into_iter()
+            .flatten()
+            .map(|(i, entry)| (*i, entry.trim_end_matches(".uasset").to_string()))
+            .collect();
+        found.extend(
+            self.layers
+                .iter()
+                .enumerate()
+                .filter_map(|(i, layer)| match &layer.source {
+                    Source::Loose(root) => root.join(&file).exists().then(|| (i, path.clone())),
+                    Source::Pak(_) => None,
+                }),
+        );
+        found.sort_by(|(a, _), (b, _)| b.cmp(a));
+        found
+    }
+
+    /// identifies the file a package is read from so caches notice when it changes
+    pub fn identity(&self, path: &str) -> Option<String> {
+        let (layer, entry) = self.lookup(path).into_iter().next()?;
+        Some(match &self.layers[layer].source {
+            Source::Pak(pak) => format!(
+                "{}:{}:{}:{entry}",
+                pak.path.to_string_lossy(),
+                pak.size,
+                pak.modified
+            ),
+            Source::Loose(root) => {
+                let file = root.join(entry + ".uasset");
+                let (size, modified) = stamp(&std::fs::metadata(&file).ok()?);
+                format!("{}:{size}:{modified}", file.to_string_lossy())
+            }
+        })
+    }
+
+    fn detect_game(&self) -> Option<String> {
+        let game = |path: &str| {
+            let mut split = path.split('/');
+            match split.next().zip(split.next()) {
+                Some((game, "Content")) if game != "Engine" => Some(game.to_string()),
+                _ => None,
+            }
+        };
+        if let Some(game) = self.packed.values().find_map(|(path, _)| game(path)) {
+            return Some(game);
+        }
+        for layer in self.layers.iter() {
+            let Source::Loose(root) = &layer.source else {
+                continue;
+            };
+            let Ok(dir) = std::fs::read_dir(root) else {
+                continue;
+            };
+            for dir in dir.filter_map(Result::ok) {
+                if !dir.file_type().is_ok_and(|t| t.is_dir()) {
+                    continue;
+                }
+                let name = dir.file_name().to_string_lossy().to_string();
+                if name != "Engine" && dir.path().join("Content").exists() {
+                    return Some(name);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// the size and modification time of a file to tell when it changes
+pub fn stamp(meta: &std::fs::Metadata) -> (u64, u64) {
+    (
+        meta.len(),
+        meta.modified()
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |time| time.as_secs()),
+    )
+}
+
+/// reads the entries of a pak from the saved index if the pak hasn't changed
+fn index(
+    path: &std::path::Path,
+    key: &Option<aes::Aes256>,
+) -> Option<(Index, Option<repak::PakReader>)> {
+    let meta = std::fs::metadata(path).ok()?;
+    let (size, modified) = stamp(&meta);
+    let saved = config().map(|config| {
+        let hash = cache::hash(path.to_string_lossy().as_bytes());
+        config.join("index").join(format!("{hash:016x}.ron"))
+    });
+    if let Some(index) = saved
+        .as_ref()
+        .and_then(|saved| std::fs::read_to_string(saved).ok())
+        .and_then(|str| ron::from_str::<Index>(&str).ok())
+        // indexes saved before sizes were kept are rebuilt
+        .filter(|index| {
+            index.path == path
+                && index.size == size
+                && index.modified == modified
+                && index.sizes.len() == index.entries.len()
+        })
+    {
+        return Some((index, None));
+    }
+    let reader = read(path, key)?;
+    let entries = reader.files();
+    // the reader only gives an entry's size by reading it so that's paid once here rather than every mount
+    let mut file = std::io::BufReader::new(std::fs::File::open(path).ok()?);
+    let sizes = entries
+        .iter()
+        .map(|entry| {
+            reader
+                .get(entry, &mut file)
+                .map_or(0, |data| data.len() as u64)
+        })
+        .collect();
+    let index = Index {
+        path: path.to_path_buf(),
+        size,
+        modified,
+        mount: reader.mount_point().to_string(),
+        entries,
+        sizes,
+    };
+    if let Some(saved) = saved {
+        if let Some(dir) = saved.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Ok(str) = ron::to_string(&index) {
+            let _ = std::fs::write(saved, str);
+        }
+    }
+    Some((index, Some(reader)))
+}
+
+/// orders paks so patches mount after what they patch
+fn priority(path: &std::path::Path) -> (bool, u32, String) {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match stem.strip_suffix("_P") {
+        Some(base) => (
+            true,
+            base.rsplit_once('_')
+                .and_then(|(_, num)| num.parse().ok())
+                .unwrap_or_default(),
+            stem,
+        ),
+        None => (false, 0, stem),
+    }
+}
+
+/// the plugin name and content folder of a mounted path inside a plugin
+fn plugin(mounted: &str) -> Option<(String, String)> {
+    if !mounted.contains("/Plugins/") {
+        return None;
+    }
+    let (content, _) = mounted.split_once("/Content/")?;
+    let name = content.rsplit('/').next()?;
+    Some((name.to_string(), content.to_string() + "/Content"))
+}
+
+fn read(path: &std::path::Path, key: &Option<aes::Aes256>) -> Option<repak::PakReader> {
+    #[cfg(target_os = "windows")]
+    #[link(name = "oo2core_win64", kind = "static")]
+    extern "C" {
+        fn OodleLZ_Decompress(
+            compBuf: *const u8,
+            compBufSize: usize,
+            rawBuf: *mut u8,
+            rawLen: usize,
+            fuzzSafe: u32,
+            checkCRC: u32,
+            verbosity: u32,
+            decBufBase: u64,
+            decBufSize: usize,
+            fpCallback: u64,
+            callbackUserData: u64,
+            decoderMemory: *mut u8,
+            decoderMemorySize: usize,
+            threadPhase: u32,
+        ) -> i32;
+    }
+    let mut pak_file = std::io::BufReader::new(std::fs::File::open(path).ok()?);
+    let mut pak = repak::PakBuilder::new();
+    if let Some(key) = key.as_ref() {
+        pak = pak.key(key.clone());
+    }
+    #[cfg(target_os = "windows")]
+    {
+        pak = pak.oodle(|| {
+            Ok(|comp_buf, raw_buf| unsafe {
+                OodleLZ_Decompress(
+                    comp_buf.as_ptr(),
+                    comp_buf.len(),
+                    raw_buf.as_mut_ptr(),
+                    raw_buf.len(),
+                    1,
+                    1,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    std::ptr::null_mut(),
+                    0,
+                    3,
+                )
+            })
+        });
+    }
+    pak.reader(&mut pak_file).ok()
+}
+
+#[test]
+fn path_with_mount() {
+    let mount = "../../../Lava/Plugins/Objects/Theme_Sets/Theme04_Wasteland/Content/Mesh/";
+    let file = "SM_TH04_Asphalt_Piece_D.uasset";
+    let mounted = mount.trim_start_matches("../../../").to_string() + file;
+    let mut vfs = Mounted {
+        game: "Lava".into(),
+        ..default()
+    };
+    let (name, content) = plugin(&mounted).unwrap();
+    assert_eq!(name, "Theme04_Wasteland");
+    vfs.plugins.insert(name, content);
+    let path = vfs.resolve("/Theme04_Wasteland/Mesh/SM_TH04_Asphalt_Piece_D");
+    assert_eq!(path + ".uasset", mounted);
+    assert_eq!(vfs.resolve("/Game/Maps/Level"), "Lava/Content/Maps/Level");
+    assert_eq!(
+        vfs.unresolve(&mounted).unwrap(),
+        "/Theme04_Wasteland/Mesh/SM_TH04_Asphalt_Piece_D.uasset"
+    );
+    assert_eq!(
+        vfs.unresolve("Lava/Content/Maps/Level.umap").unwrap(),
+        "/Game/Maps/Level.umap"
+    );
+}
+
+#[test]
+fn patch_priority() {
+    let mut paks = [
+        "pakchunk0_2_P.pak",
+        "pakchunk0-WindowsNoEditor.pak",
+        "pakchunk0_P.pak",
+        "pakchunk0_10_P.pak",
+    ]
+    .map(|name| priority(std::path::Path::new(name)));
+    paks.sort();
+    assert_eq!(
+        paks.map(|(.., name)| name),
+        [
+            "pakchunk0-WindowsNoEditor",
+            "pakchunk0_P",
+            "pakchunk0_2_P",
+            "pakchunk0_10_P"
+        ]
+    );
+}
+
+#[test]
+fn case_insensitive() {
+    let mut vfs = Mounted {
+        game: "Lava".into(),
+        ..default()
+    };
+    vfs.add(
+        0,
+        "Lava/Content/Maps/Level.uasset".into(),
+        "Content/Maps/Level.uasset".into(),
+        16,
+    );
+    assert_eq!(
+        vfs.lookup("/Game/maps/LEVEL"),
+        [(0, "Content/Maps/Level".to_string())]
+    );
+    assert_eq!(vfs.size("lava/content/maps/level.uasset"), Some(16));
+    // the original spelling is what's listed
+    assert!(vfs.files().contains("Lava/Content/Maps/Level.uasset"));
+}